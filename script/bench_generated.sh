@@ -0,0 +1,31 @@
+#!/usr/bin/env bash
+
+# Time the connectivity sweeps on a generated stream with many nodes with the current binary and
+# the one of a baseline revision, usage: bench_generated.sh <delta> [nbNodes] [revision]
+# The default baseline is the last revision copying the whole distance matrix at each time.
+CMD="../target/release/linkstream"
+NBNODES=${2:-1000}
+BASE=${3:-76a9496}
+DELTA=$1
+OLDDIR=`pwd`
+
+cd script
+BASEDIR=`mktemp -d`
+rmdir $BASEDIR
+./build_revision.sh $BASE $BASEDIR || exit 1
+STREAM=`mktemp`
+$CMD gen $NBNODES 2000 0.0001 --seed 3 > $STREAM
+echo "$NBNODES nodes, `wc -l < $STREAM` links"
+
+for BENCH in "calc connexity $DELTA $NBNODES" "calc comps $DELTA $NBNODES" "calc comps up $DELTA $NBNODES"
+do
+    echo "$BENCH"
+    TIMEFORMAT="    $BASE: %R s"
+    time ($BASEDIR/target/release/linkstream $BENCH < $STREAM > /dev/null)
+    TIMEFORMAT="    current: %R s"
+    time ($CMD $BENCH < $STREAM > /dev/null)
+done
+
+rm $STREAM
+git worktree remove --force $BASEDIR
+cd $OLDDIR
@@ -0,0 +1,31 @@
+#!/usr/bin/env bash
+
+# Time the connectivity sweeps on rollernet with the current binary and the one of a baseline revision,
+# usage: bench_rollernet.sh <delta in seconds> [revision]
+# The default baseline is the last revision copying the whole distance matrix at each time.
+CMD="../target/release/linkstream"
+BASE=${2:-76a9496}
+DATA="../datas/rollernet.dyn.gz"
+NBNODES=62
+DELTA=$1
+OLDDIR=`pwd`
+
+cd script
+BASEDIR=`mktemp -d`
+rmdir $BASEDIR
+./build_revision.sh $BASE $BASEDIR || exit 1
+STREAM=`mktemp`
+zcat $DATA > $STREAM
+
+for BENCH in "calc connexity $DELTA $NBNODES" "calc comps $DELTA $NBNODES" "calc comps up $DELTA $NBNODES" "calc part $DELTA $NBNODES"
+do
+    echo "$BENCH"
+    TIMEFORMAT="    $BASE: %R s"
+    time ($BASEDIR/target/release/linkstream $BENCH < $STREAM > /dev/null)
+    TIMEFORMAT="    current: %R s"
+    time ($CMD $BENCH < $STREAM > /dev/null)
+done
+
+rm $STREAM
+git worktree remove --force $BASEDIR
+cd $OLDDIR
@@ -0,0 +1,6 @@
+#!/usr/bin/env bash
+
+# Build the linkstream binary of a git revision in a new worktree, usage: build_revision.sh <revision> <directory>
+# The binary is <directory>/target/release/linkstream, remove the worktree with git worktree remove --force <directory>.
+git worktree add -q --detach $2 $1 || exit 1
+(cd $2 && cargo build --release -q) || exit 1
@@ -35,36 +35,12 @@ zcat $DATA | $CMD calc part up $DELTA $NBNODES > $OUTDIR"part_up.dat"
 # done
 
 # ### Number of existence intervals
-# OUT=$OUTDIR"cut.dat"
-# rm $OUT
-# for ((i=1; i <= 200; i++)) do
-# (
-#     ((number=i*86400*3))
-#     echo "number of existence intervs for delta = "$number
-#     echo $number `zcat $DATA | $CMD calc exist cut $number $NBNODES | wc -l` >> $OUT
-# )
-# done
+# zcat $DATA | $CMD calc sweep 259200 51840000 259200 cut $NBNODES > $OUTDIR"cut.dat"
 
 # ### Largest rectangle
-# OUT=$OUTDIR"lr.dat"
-# rm $OUT
-# for ((i=1; i <= 160; i++)) do
-# (
-#     ((number=i*86400*3))
-#     echo "largest rectangle : "$number
-#     echo $number `zcat $DATA | $CMD calc exist lr $number $NBNODES` >> $OUT
-# )
-# done
+# zcat $DATA | $CMD calc sweep 259200 41472000 259200 lr $NBNODES > $OUTDIR"lr.dat"
 
 # ### Boxes medium size
-# OUT=$OUTDIR"box_moy.dat"
-# rm box_moy.dat
-# for ((i=1; i <= 360; i++)) do
-# (
-#     ((number=i*84600*3))
-#     echo "medium boxe size for delta = "$number
-#     echo $number `zcat $DATA | $CMD calc exist cut $number $NBNODES | awk '{n1+=($2-$1)*($NF - 2); n2+=1} END { print n1/n2/(150*98277034)}' `>> $OUT
-# )
-# done
+# zcat $DATA | $CMD calc sweep 253800 91368000 253800 boxes $NBNODES | awk '{ print $1, $2/(150*98277034) }' > $OUTDIR"box_moy.dat"
 
 cd $OLDDIR
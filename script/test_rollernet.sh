@@ -35,36 +35,12 @@ zcat $DATA | $CMD calc part up $DELTA $NBNODES > $OUTDIR"part_up.dat"
 # done
 
 # ### Number of existence intervals
-# OUT=$OUTDIR"cut.dat"
-# rm $OUT
-# for ((i=1; i <= 200; i++)) do
-# (
-#     ((number=i))
-#     echo "number of existence intervs for delta = "$number
-#     echo $number `zcat $DATA | $CMD calc exist cut $number $NBNODES | wc -l` >> $OUT
-# )
-# done
+# zcat $DATA | $CMD calc sweep 1 200 1 cut $NBNODES > $OUTDIR"cut.dat"
 
 # ### Largest rectangle
-# OUT=$OUTDIR"lr.dat"
-# rm $OUT
-# for ((i=1; i <= 160; i++)) do
-# (
-#     ((number=i*3))
-#     echo "largest rectangle : "$number
-#     echo $number `zcat $DATA | $CMD calc exist lr $number $NBNODES` >> $OUT
-# )
-# done
+# zcat $DATA | $CMD calc sweep 3 480 3 lr $NBNODES > $OUTDIR"lr.dat"
 
 # ### Boxes medium size
-# OUT=$OUTDIR"box_moy.dat"
-# rm box_moy.dat
-# for ((i=1; i <= 400; i++)) do
-# (
-#     ((number=i*10))
-#     echo "medium boxe size for delta = "$number
-#     echo $number `zcat $DATA | $CMD calc exist cut $number $NBNODES | awk '{n1+=($2-$1)*($NF - 2); n2+=1} END { print n1/n2/(62*9976)}' `>> $OUT
-# )
-# done
+# zcat $DATA | $CMD calc sweep 10 4000 10 boxes $NBNODES | awk '{ print $1, $2/(62*9976) }' > $OUTDIR"box_moy.dat"
 
     cd $OLDDIR
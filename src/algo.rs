@@ -1,105 +1,169 @@
 use data::link::*;
 use data::matrix::*;
+use data::bitset::{BitMatrix, BitSet};
 use data::filtre::NodeFilter;
 use data::filtre::TimeFilter;
 use data::filtre;
 use data::iterators::link_iterator::LinkIterator;
 use std::cmp::min;
-use std::collections::HashMap;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+pub mod cliques;
+pub mod stats;
+pub mod check;
+pub mod motifs;
+pub mod communities;
+pub mod spreading;
+pub mod benchmark;
 
 
 // ////////////////////////////
 //          CONNECTIVITY
 // ////////////////////////////
 
-/// Return a vector where each element (t, bool) tells us if the linkstream
-/// reduce to [t, t + delta] is connected. The linkstream is delta-connected if
-/// each element of the vector is (t, true) for t <= tmax - delta.
-pub fn is_delta_connected(links: &mut LinkIterator,
-                          delta: Time,
-                          size: usize,
-                          nfilter: &NodeFilter,
-                          tfilter: &TimeFilter) -> Vec<(Time, bool)> {
-    let max_val: Time = Time::max_value();
-    let mut resultat: Vec<(Time, bool)> = Vec::new();
-    let mut dist: Matrix<Time> = Matrix::new(max_val, size, size);
-    let mut p_dist: Matrix<Time> = Matrix::new(max_val, size, size);
-    let mut curr: Time = max_val;
-    let mut pcurr: Time = curr;
-    let mut first: bool = true;
-    for link in links {
-        if filtre::combine(link, nfilter, tfilter){
-            maj_distance(link, &mut dist, &mut p_dist, &mut curr);
-            if first { pcurr = curr; first = false; }
-            if curr != pcurr {
-                pcurr = curr;
-                let res = dist.is_subset_delta_clique(pcurr, delta, nfilter);
-                resultat.push((curr, res));
-                println!("{} {}", curr, res);
-            }
+/// Lazily yield elements (t, bool) telling if the linkstream reduce to [t, t + delta]
+/// is connected. The linkstream is delta-connected if each element is (t, true) for
+/// t <= tmax - delta. Links are read as the results are consumed.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::is_delta_connected;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![Link { node1: 0, node2: 1, time: 10 }, Link { node1: 0, node2: 1, time: 5 }];
+/// let mut iter = links.into_iter();
+/// let results: Vec<_> = is_delta_connected(&mut iter, 5, 2, &|_| true, &|_| true).collect();
+/// assert_eq!(results, vec![(5, true)]);
+/// ```
+pub fn is_delta_connected<'a>(links: &'a mut LinkIterator,
+                              delta: Time,
+                              size: usize,
+                              nfilter: &'a NodeFilter,
+                              tfilter: &'a TimeFilter) -> impl Iterator<Item=(Time, bool)> + 'a {
+    let mut connectivity = DeltaConnectivity::new(delta, size, nfilter, tfilter);
+    links.filter_map(move |link| connectivity.push(link))
+}
+
+/// Online delta-connectivity of a linkstream, fed one link at a time.
+///
+/// Links must be pushed by decreasing time, as they come in a linkstream. Each time the
+/// timestamp changes, `push` returns `(t, bool)` telling if the links pushed so far, reduced
+/// to [t, t + delta], are connected, as `is_delta_connected` does.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::DeltaConnectivity;
+/// # use linkstreams::data::link::Link;
+/// let mut connectivity = DeltaConnectivity::new(3, 3, &|_| true, &|_| true);
+/// assert_eq!(connectivity.push(Link { node1: 0, node2: 1, time: 10 }), None);
+/// // 0 can not reach 2 yet
+/// assert_eq!(connectivity.push(Link { node1: 1, node2: 2, time: 9 }), Some((9, false)));
+/// assert_eq!(connectivity.push(Link { node1: 0, node2: 1, time: 8 }), Some((8, true)));
+/// assert_eq!(connectivity.push(Link { node1: 0, node2: 1, time: 2 }), Some((2, false)));
+/// ```
+pub struct DeltaConnectivity<'a> {
+    delta: Time,
+    nfilter: &'a NodeFilter,
+    tfilter: &'a TimeFilter,
+    distances: DistanceSweep<Distances>,
+    pcurr: Time,
+    first: bool,
+}
+
+impl<'a> DeltaConnectivity<'a> {
+    pub fn new(delta: Time, size: usize, nfilter: &'a NodeFilter, tfilter: &'a TimeFilter) -> DeltaConnectivity<'a> {
+        DeltaConnectivity {
+            delta, nfilter, tfilter,
+            distances: DistanceSweep::new(Distances::new(size), Distances::new(size)),
+            pcurr: Time::MAX,
+            first: true,
         }
     }
-    resultat
-}
 
-// Update distance matrix with a new link.
-fn maj_distance(link: Link,
-                dist: &mut Matrix<Time>,
-                p_dist: &mut Matrix<Time>,
-                curr: &mut Time) {
-    let (u, v, t) = (link.node1, link.node2, link.time);
-    if *curr == Time::max_value() {
-        *curr = t;
-        dist.diag(t);
+    /// Push the next link of the stream, returning the connectivity at the new timestamp if it changed.
+    pub fn push(&mut self, link: Link) -> Option<(Time, bool)> {
+        if !filtre::combine(link, self.nfilter, self.tfilter) { return None; }
+        self.distances.push(link);
+        let curr = self.distances.curr;
+        if self.first { self.pcurr = curr; self.first = false; }
+        if curr == self.pcurr { return None; }
+        self.pcurr = curr;
+        Some((curr, self.distances.dist.is_subset_delta_clique(curr, self.delta, self.nfilter)))
     }
-    if *curr != t {
-        p_dist.copy(dist);
-        dist.diag(t);
-        *curr = t;
+}
+
+// Distances at the current timestamp and at the previous one, updated link by link.
+//
+// When the timestamp changes, the previous distances become the current ones. As a link
+// only changes the rows of its nodes, only the rows changed since the previous timestamp
+// are copied, instead of the whole matrix. Diagonals of the previous distances are never
+// read and are not kept up to date.
+struct DistanceSweep<D: DistMatrix> {
+    dist: D,
+    p_dist: D,
+    curr: Time,
+    dirty: Vec<Node>,
+    is_dirty: Vec<bool>,
+}
+
+impl<D: DistMatrix> DistanceSweep<D> {
+    fn new(dist: D, p_dist: D) -> DistanceSweep<D> {
+        let size = dist.size();
+        DistanceSweep { dist, p_dist, curr: Time::MAX, dirty: Vec::new(), is_dirty: vec![false; size] }
     }
-    for i in 0..dist.width {
-        if u == i {
-            dist.set(v, i, t);
+
+    fn mark(&mut self, node: Node) {
+        if !self.is_dirty[node] {
+            self.is_dirty[node] = true;
+            self.dirty.push(node);
         }
-        else if v == i {
-            dist.set(u, i, t);
+    }
+
+    // Update distance matrix with a new link.
+    fn push(&mut self, link: Link) {
+        let (u, v, t) = (link.node1, link.node2, link.time);
+        if self.curr == Time::MAX {
+            self.curr = t;
+            self.dist.diag(t);
         }
-        else {
-            if p_dist.get(u, i) > t {
-                let min_res = min(dist.get(v, i), p_dist.get(u, i));
-                dist.set(v, i, min_res);
-            }
-            if p_dist.get(v, i) > t {
-                let min_res = min(dist.get(u, i), p_dist.get(v, i));
-                dist.set(u, i, min_res);
+        if self.curr != t {
+            for node in self.dirty.drain(..) {
+                self.p_dist.copy_row(&self.dist, node);
+                self.is_dirty[node] = false;
             }
+            self.dist.diag(t);
+            self.curr = t;
         }
+        self.mark(u);
+        self.mark(v);
+        self.dist.add_link(&self.p_dist, u, v, t);
     }
 }
 
 /// the delta-reachability-graph is the delta-reachability relation graph :
-/// if u can delta-reach v then the index (u,v) equals 1, else it equals 0.
+/// if u can delta-reach v then the index (u,v) is set, else it is not.
+///
+/// Distances are stored in a dense or a sparse matrix depending on `size`, see `Distances`.
 pub fn delta_reachability_graph(links: &mut LinkIterator,
                                 delta: Time,
                                 size: usize,
                                 nfilter: &NodeFilter,
-                                tfilter: &TimeFilter) -> Matrix<Time> {
-    let max_val: Time = Time::max_value();
-    let mut dist: Matrix<Time> = Matrix::new(max_val, size, size);
-    let mut p_dist: Matrix<Time> = Matrix::new(max_val, size, size);
-    let mut reach: Matrix<Time> = Matrix::new(1, size, size);
-    let mut curr: Time = max_val;
-    let mut pcurr: Time = curr;
+                                tfilter: &TimeFilter) -> BitMatrix {
+    let max_val: Time = Time::MAX;
+    let mut distances = DistanceSweep::new(Distances::new(size), Distances::new(size));
+    let mut reach = BitMatrix::new(size, true);
+    let mut pcurr: Time = max_val;
     let mut first: bool = true;
     let mut tmax: Time = max_val;
     for link in links {
         if filtre::combine(link, nfilter, tfilter){
-            maj_distance(link, &mut dist, &mut p_dist, &mut curr);
+            distances.push(link);
+            let curr = distances.curr;
             if first { pcurr = curr; first = false; tmax = link.time}
             if curr != pcurr {
                 pcurr = curr;
                 if curr < tmax - delta{
-                    maj_reach_graph(&mut reach, &dist, link.time, delta);
+                    maj_reach_graph(&mut reach, &distances.dist, link.time, delta);
                 }
             }
         }
@@ -108,16 +172,12 @@ pub fn delta_reachability_graph(links: &mut LinkIterator,
 }
 
 // Update the delta-reachability graph using the distance matrix
-fn maj_reach_graph(reach: &mut Matrix<Time>,
-                   dist: &Matrix<Time>, time: Time, delta: Time) {
-    for i in 0..dist.width {
-        for j in 0..dist.height {
-            if reach.get(i, j) == 1 {
-                if dist.get(i, j) - time > delta {
-                    reach.set(i, j, 0);
-                }
-            }
-        }
+fn maj_reach_graph<D: DistMatrix>(reach: &mut BitMatrix,
+                                  dist: &D, time: Time, delta: Time) {
+    // pairs only leave the graph, so only the remaining ones are checked
+    for i in 0..dist.size() {
+        let far: Vec<Node> = reach.row(i).iter().filter(|&j| dist.get(i, j) - time > delta).collect();
+        for j in far { reach.remove(i, j); }
     }
 }
 
@@ -151,11 +211,10 @@ fn maj_reach_graph(reach: &mut Matrix<Time>,
 /// let order_2 = vec![2, 1, 3, 0];
 /// assert_eq!(vec![1, 0, 3], dfs(&mat, &order_2, &nodefilter_2));
 /// ```
-pub fn dfs(mat: &Matrix<Time>, order: &Vec<Node>, nodefilter: &Vec<bool>) -> Vec<Node> {
-    assert!(nodefilter.len() == mat.width);
-    let mut result: Vec<Node> = Vec::with_capacity(mat.width);
-    let mut marks: Vec<bool> = Vec::with_capacity(mat.width);
-    for _ in 0..mat.width { marks.push(false); }
+pub fn dfs<G: ReachGraph>(mat: &G, order: &[Node], nodefilter: &[bool]) -> Vec<Node> {
+    assert!(nodefilter.len() == mat.size());
+    let mut result: Vec<Node> = Vec::with_capacity(mat.size());
+    let mut marks: Vec<bool> = vec![false; mat.size()];
     let mut stack: Vec<Node> = Vec::new();
     for refnode in order {
         let &node = refnode;
@@ -212,11 +271,10 @@ pub fn dfs(mat: &Matrix<Time>, order: &Vec<Node>, nodefilter: &Vec<bool>) -> Vec
 /// let result = vec![vec![0, 1, 2, 3]];
 /// assert_eq!(result, connected_component(&mat, &order, &nodefilter));
 /// ```
-pub fn connected_component(mat: &Matrix<Time>, order: &Vec<Node>, nodefilter: &Vec<bool>) -> Vec<Vec<Node>> {
+pub fn connected_component<G: ReachGraph>(mat: &G, order: &[Node], nodefilter: &[bool]) -> Vec<Vec<Node>> {
     let dfsorder = dfs(mat, order, nodefilter);
     let mut result: Vec<Vec<Node>> = Vec::new();
-    let mut marks: Vec<bool> = Vec::with_capacity(mat.width);
-    for _ in 0..mat.width { marks.push(false); }
+    let mut marks: Vec<bool> = vec![false; mat.size()];
     let mut stack: Vec<Node> = Vec::new();
     for node in dfsorder {
         if nodefilter[node] && marks[node] == false {
@@ -237,27 +295,36 @@ pub fn connected_component(mat: &Matrix<Time>, order: &Vec<Node>, nodefilter: &V
     result
 }
 
-///
+/// Lower bound of the delta-components of the stream, see `reach_components_lower`.
 pub fn delta_components_lower(links: &mut LinkIterator, size: usize,
-                        delta: Time, filter: &Vec<Node>, tfilter: &TimeFilter) -> (Vec<Vec<Node>>, Vec<Vec<Node>>) {
+                        delta: Time, filter: &[Node], tfilter: &TimeFilter) -> (Vec<Vec<Node>>, Vec<Vec<Node>>) {
+    let reach_graph = delta_reachability_graph(links, delta, size, &|_| true, tfilter);
+    reach_components_lower(&reach_graph, filter)
+}
+
+/// Split `filter` into cliques of the delta-reachability graph.
+///
+/// Strongly connected components which are not cliques are cut by evicting a node, until only cliques remain.
+/// Each returned component is a true delta-component, but they may be smaller than the real ones.
+pub fn reach_components_lower<G: ReachGraph>(reach_graph: &G, filter: &[Node]) -> (Vec<Vec<Node>>, Vec<Vec<Node>>) {
+    let size = reach_graph.size();
     let order: Vec<Node> = (0..size).collect();
     let mut components: Vec<Vec<Node>> = Vec::new();
     let reste: Vec<Vec<Node>> = Vec::new();
     let mut stack: Vec<Vec<Node>> = Vec::new();
-    let reach_graph: Matrix<Time> = delta_reachability_graph(links, delta, size, &|_| true, tfilter);
-    stack.push(filter.clone());
+    stack.push(filter.to_vec());
     while let Some(filter) = stack.pop() {
         let filter_clone = filter.clone();
         if reach_graph.is_subset_clique(&move |node| filter_clone.contains(&node)) {
             if filter.len() > 1 {components.push(filter);};
         }
         else {
-            let cuts: Vec<Vec<Node>> = connected_component(&reach_graph, &order, &filtre::node_filter(&filter, size));
+            let cuts: Vec<Vec<Node>> = connected_component(reach_graph, &order, &filtre::node_filter(&filter, size));
             for comp in cuts {
                 if comp.len() == filter.len() {
                     let mut comp_clone = comp.clone();
                     let comp_filtre = comp.clone();
-                    let mnode: Node = reach_graph.get_max_deg_node(&move |node| comp_filtre.contains(&node)) as Node;
+                    let mnode: Node = reach_graph.get_min_deg_node(&move |node| comp_filtre.contains(&node)) as Node;
                     comp_clone.sort();
                     if let Ok(n)= comp_clone.binary_search(&mnode) {
                         comp_clone.remove(n);
@@ -272,22 +339,31 @@ pub fn delta_components_lower(links: &mut LinkIterator, size: usize,
     (components, reste)
 }
 
-///
+/// Upper bound of the delta-components of the stream, see `reach_components_upper`.
 pub fn delta_components_upper(links: &mut LinkIterator, size: usize,
-                        delta: Time, filter: &Vec<Node>, tfilter: &TimeFilter) -> (Vec<Vec<Node>>, Vec<Vec<Node>>) {
+                        delta: Time, filter: &[Node], tfilter: &TimeFilter) -> (Vec<Vec<Node>>, Vec<Vec<Node>>) {
+    let reach_graph = delta_reachability_graph(links, delta, size, &|_| true, tfilter);
+    reach_components_upper(&reach_graph, filter)
+}
+
+/// Split `filter` into strongly connected components of the delta-reachability graph.
+///
+/// Components which are cliques are returned first, the others are returned in the second vector.
+/// Every delta-component is included in one of the returned sets.
+pub fn reach_components_upper<G: ReachGraph>(reach_graph: &G, filter: &[Node]) -> (Vec<Vec<Node>>, Vec<Vec<Node>>) {
+    let size = reach_graph.size();
     let order: Vec<Node> = (0..size).collect();
     let mut components: Vec<Vec<Node>> = Vec::new();
     let mut reste: Vec<Vec<Node>> = Vec::new();
     let mut stack: Vec<Vec<Node>> = Vec::new();
-    let reach_graph: Matrix<Time> = delta_reachability_graph(links, delta, size, &|_| true, tfilter);
-    stack.push(filter.clone());
+    stack.push(filter.to_vec());
     while let Some(filter) = stack.pop() {
         let filter_clone = filter.clone();
         if reach_graph.is_subset_clique(&move |node| filter_clone.contains(&node)) {
             if filter.len() > 1 { components.push(filter); }
         }
         else {
-            let cuts: Vec<Vec<Node>> = connected_component(&reach_graph, &order, &filtre::node_filter(&filter, size));
+            let cuts: Vec<Vec<Node>> = connected_component(reach_graph, &order, &filtre::node_filter(&filter, size));
             for comp in cuts {
                 if comp.len() == filter.len() {
                     reste.push(comp);
@@ -299,63 +375,184 @@ pub fn delta_components_upper(links: &mut LinkIterator, size: usize,
     (components, reste)
 }
 
+/// Exact delta-components of the stream, see `reach_components_exact`.
+pub fn delta_components_exact(links: &mut LinkIterator, size: usize,
+                              delta: Time, filter: &[Node], tfilter: &TimeFilter,
+                              budget: usize) -> (Vec<Vec<Node>>, bool) {
+    let reach_graph = delta_reachability_graph(links, delta, size, &|_| true, tfilter);
+    reach_components_exact(&reach_graph, filter, budget)
+}
 
-pub fn delta_partition(links: &mut LinkIterator,nodes: &Vec<Node>, delta: Time, upper: bool) -> Vec<(Time, Time, (Vec<Vec<Node>>, Vec<Vec<Node>>))> {
+/// Enumerate the delta-components of `filter`, that is the maximal sets of nodes
+/// which can all delta-reach each other.
+///
+/// Components are the maximal cliques of the mutual delta-reachability graph and are
+/// enumerated with the Bron-Kerbosch algorithm. As there may be exponentially many of them,
+/// the search stops after `budget` steps; the returned boolean tells if the enumeration is complete.
+/// Components are sorted by decreasing size, singletons are omitted.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::matrix::*;
+/// # use linkstreams::data::link::*;
+/// # use linkstreams::algo::reach_components_exact;
+/// let mat: Matrix<Time> = Matrix::parse(vec![
+///     vec![1, 1, 1, 0],
+///     vec![1, 1, 1, 1],
+///     vec![1, 1, 1, 1],
+///     vec![0, 1, 1, 1]
+///         ]);
+/// let filter = vec![0, 1, 2, 3];
+/// assert_eq!(reach_components_exact(&mat, &filter, 100), (vec![vec![0, 1, 2], vec![1, 2, 3]], true));
+/// assert_eq!(reach_components_exact(&mat, &vec![0, 3], 100), (vec![], true));
+/// assert!(!reach_components_exact(&mat, &filter, 1).1);
+/// ```
+pub fn reach_components_exact<G: ReachGraph>(reach_graph: &G, filter: &[Node], budget: usize) -> (Vec<Vec<Node>>, bool) {
+    let size = reach_graph.size();
+    let mut adj: Matrix<bool> = Matrix::new(false, size, size);
+    for &u in filter {
+        for &v in filter {
+            if u != v && reach_graph.reaches(u, v) && reach_graph.reaches(v, u) { adj.set(u, v, true); }
+        }
+    }
+    let mut components: Vec<Vec<Node>> = Vec::new();
+    let mut budget = budget;
+    let complete = bron_kerbosch(&adj, &mut Vec::new(), filter.to_vec(), Vec::new(), &mut components, &mut budget);
+    for comp in components.iter_mut() { comp.sort(); }
+    components.sort_by(|c1, c2| c2.len().cmp(&c1.len()).then_with(|| c1.cmp(c2)));
+    (components, complete)
+}
+
+// Bron-Kerbosch with pivoting. Return false when the budget is exhausted.
+fn bron_kerbosch(adj: &Matrix<bool>, clique: &mut Vec<Node>,
+                 mut candidates: Vec<Node>, mut excluded: Vec<Node>,
+                 cliques: &mut Vec<Vec<Node>>, budget: &mut usize) -> bool {
+    if *budget == 0 { return false; }
+    *budget -= 1;
+    if candidates.is_empty() {
+        if excluded.is_empty() && clique.len() > 1 { cliques.push(clique.clone()); }
+        return true;
+    }
+    let degree = |u: Node| candidates.iter().filter(|&&v| adj.get(u, v)).count();
+    let pivot = *candidates.iter().chain(excluded.iter()).max_by_key(|&&u| degree(u)).unwrap();
+    let branches: Vec<Node> = candidates.iter().cloned().filter(|&v| !adj.get(pivot, v)).collect();
+    for v in branches {
+        clique.push(v);
+        let next_candidates = candidates.iter().cloned().filter(|&w| adj.get(v, w)).collect();
+        let next_excluded = excluded.iter().cloned().filter(|&w| adj.get(v, w)).collect();
+        let done = bron_kerbosch(adj, clique, next_candidates, next_excluded, cliques, budget);
+        clique.pop();
+        if !done { return false; }
+        candidates.retain(|&w| w != v);
+        excluded.push(v);
+    }
+    true
+}
+
+/// Split the stream into its existence intervals, and each interval into delta-components.
+///
+/// Intervals are independent, so their components are computed in parallel on the rayon
+/// thread pool, each from the links of its interval only. Results are in the order of the intervals.
+/// The stream must be sorted by decreasing time, as the links of an interval are found by binary search.
+pub fn delta_partition(links: &mut LinkIterator, nodes: &[Node], delta: Time, upper: bool) -> Vec<(Time, Time, (Vec<Vec<Node>>, Vec<Vec<Node>>))> {
     let links: Vec<Link> = links.collect();
+    debug_assert!(links.windows(2).all(|w| w[0].time >= w[1].time), "Links are not sorted by decreasing time");
     let mut iter = links.clone().into_iter();
-    let mut res: Vec<(Time, Time, (Vec<Vec<Node>>, Vec<Vec<Node>>))> = Vec::new();
     let intervals = existence_intervals(&mut iter, nodes, delta);
-    for interv in intervals {
+    intervals.into_par_iter().map(|interv| {
         let (stop, start, vec) = interv;
-        let comps: (Vec<Vec<Node>>, Vec<Vec<Node>>);
-        comps = if upper {
-            delta_components_upper(&mut links.clone().into_iter(), nodes.len(), delta, &vec, &move |time: Time| {time >=start && time <= stop + delta + 1})
+        // links are sorted by decreasing time
+        let first = links.partition_point(|link| link.time > stop + delta + 1);
+        let last = links.partition_point(|link| link.time >= start);
+        // the components need an owned stream
+        let window: Vec<Link> = links[first..last.max(first)].to_vec();
+        let comps = if upper {
+            delta_components_upper(&mut window.into_iter(), nodes.len(), delta, &vec, &move |time: Time| {time >=start && time <= stop + delta + 1})
                 } else {
-            delta_components_lower(&mut links.clone().into_iter(), nodes.len(), delta, &vec, &move |time: Time| {time >=start && time <= stop + delta + 1})
+            delta_components_lower(&mut window.into_iter(), nodes.len(), delta, &vec, &move |time: Time| {time >=start && time <= stop + delta + 1})
                 };
-        res.push((start.clone(), stop.clone(), comps.clone()));
-    }
-    res
+        (start, stop, comps)
+    }).collect()
 }
 
 // ////////////////////////////
 //        EXISTENCE
 // ////////////////////////////
-pub fn delta_existence(links: &mut LinkIterator,
-                       nodes: &Vec<Node>, delta: Time) -> Vec<(Time, Vec<bool>)> {
-    let mut results: Vec<(Time, Vec<bool>)> = Vec::new();
-    let mut map: HashMap<Node, Node> = HashMap::new();
-    for i in 0..nodes.len() { map.insert(nodes[i], i); }
-    let map = map;
-    let mut record: Vec<Time> = Vec::with_capacity(nodes.len());
-    let mval = Time::max_value();
-    let mut t_curr = mval;
-    let mut t_max: Time = 0;
-    for _ in 0..nodes.len() { record.push(mval); }
-    let is_existing = |t_curr: Time, record: &Vec<Time>| {
-        let res: Vec<bool> = record.iter().map(|time| time - t_curr < delta).collect();
-        res
-    };
-    for link in links {
-        let (n1, n2, t) = (link.node1, link.node2, link.time);
-        if t_curr == mval {
-            t_curr = t;
-            t_max = t;
-        }
-        else if t_curr != t {
-            if t < t_max - delta {
-                results.push((t, is_existing(t_curr, &record)));
+/// Per-node last-seen times of a linkstream, recorded once and replayable for any delta.
+///
+/// The delta-existence of a node only depends on the last time it was seen, so the
+/// stream is reduced to the list of nodes seen at each timestamp. Existence traces
+/// for several deltas can then be computed without reading the stream again.
+pub struct LastSeenTrace {
+    /// Greatest time of the stream
+    pub t_max: Time,
+    size: usize,
+    steps: Vec<(Time, Time, Vec<usize>)>,
+}
+
+impl LastSeenTrace {
+    /// Record the last-seen times of `nodes` in the linkstream.
+    pub fn new(links: &mut LinkIterator, nodes: &[Node]) -> LastSeenTrace {
+        let mut map: HashMap<Node, usize> = HashMap::new();
+        for (i, &node) in nodes.iter().enumerate() { map.insert(node, i); }
+        let mval = Time::MAX;
+        let mut t_curr = mval;
+        let mut t_max: Time = 0;
+        let mut seens: Vec<usize> = Vec::new();
+        let mut steps: Vec<(Time, Time, Vec<usize>)> = Vec::new();
+        for link in links {
+            let (n1, n2, t) = (link.node1, link.node2, link.time);
+            if t_curr == mval {
+                t_curr = t;
+                t_max = t;
+            }
+            else if t_curr != t {
+                steps.push((t, t_curr, seens));
+                seens = Vec::new();
+                t_curr = t;
             }
-            t_curr = t;
+            if let Some(&bij) = map.get(&n1) { seens.push(bij); }
+            if let Some(&bij) = map.get(&n2) { seens.push(bij); }
         }
-        if map.contains_key(&n1) {
-            let &bij = map.get(&n1).unwrap();
-            record[bij] = t; }
-        if map.contains_key(&n2) {
-            let &bij = map.get(&n2).unwrap();
-            record[bij] = t; }
+        LastSeenTrace { t_max, size: nodes.len(), steps }
     }
-    results
+
+    /// Replay the trace and return the delta-existence of each node, as `delta_existence` does.
+    ///
+    /// # Example
+    /// ```
+    /// # use linkstreams::algo::LastSeenTrace;
+    /// # use linkstreams::data::link::Link;
+    /// let links = vec![
+    ///     Link {node1: 0, node2: 1, time: 10},
+    ///     Link {node1: 1, node2: 2, time: 8},
+    ///     Link {node1: 0, node2: 2, time: 3},
+    ///     Link {node1: 0, node2: 1, time: 1},
+    /// ];
+    /// let trace = LastSeenTrace::new(&mut links.into_iter(), &vec![0, 1, 2]);
+    /// assert_eq!(trace.existence(5), vec![(3, vec![true, true, true]),
+    ///                                     (1, vec![true, false, true])]);
+    /// assert_eq!(trace.existence(2), vec![(3, vec![false, true, true]),
+    ///                                     (1, vec![true, false, true])]);
+    /// ```
+    pub fn existence(&self, delta: Time) -> Vec<(Time, Vec<bool>)> {
+        let mut results: Vec<(Time, Vec<bool>)> = Vec::new();
+        let mut record: Vec<Time> = vec![Time::MAX; self.size];
+        let bound = self.t_max.checked_sub(delta);
+        for &(t, t_prev, ref seens) in &self.steps {
+            for &bij in seens { record[bij] = t_prev; }
+            if bound.is_some_and(|bound| t < bound) {
+                results.push((t, record.iter().map(|time| time - t_prev < delta).collect()));
+            }
+        }
+        results
+    }
+}
+
+/// Delta-existence trace of the nodes, see `LastSeenTrace::existence`.
+pub fn delta_existence(links: &mut LinkIterator,
+                       nodes: &[Node], delta: Time) -> Vec<(Time, Vec<bool>)> {
+    LastSeenTrace::new(links, nodes).existence(delta)
 }
 /// The classic and boolean operator for boolean vector
 ///
@@ -428,11 +625,17 @@ pub fn intersection(v1: &Vec<Node>, v2: &Vec<Node>) -> Vec<Node> {
     res
 }
 
+/// Constant delta-existence intervals of the nodes, see `intervals_from_trace`.
 pub fn existence_intervals(links: &mut LinkIterator,
-                           nodes: &Vec<Node>, delta: Time)
+                           nodes: &[Node], delta: Time)
                            -> Vec<(Time, Time, Vec<Node>)>{
-    let trace: Vec<(Time, Vec<bool>)> = delta_existence(links, nodes, delta);
+    intervals_from_trace(&delta_existence(links, nodes, delta))
+}
+
+/// Cut a delta-existence trace into its constant existence intervals.
+pub fn intervals_from_trace(trace: &[(Time, Vec<bool>)]) -> Vec<(Time, Time, Vec<Node>)> {
     let mut intervals: Vec<(Time, Time, Vec<Node>)> = Vec::new();
+    if trace.is_empty() { return intervals; }
     let mut curr_vec: Vec<bool>;
     let mut start: Time;
     let mut prev: Time;
@@ -442,8 +645,8 @@ pub fn existence_intervals(links: &mut LinkIterator,
         start = borrow_start.clone();
         prev = start.clone();
     }
-    for (tcurr, mask) in trace {
-        if diff(&curr_vec, &mask) {
+    for &(tcurr, ref mask) in trace {
+        if diff(&curr_vec, mask) {
             intervals.push((start, prev, boolvec_to_set(&curr_vec)));
             start = tcurr;
             curr_vec = mask.clone();
@@ -454,32 +657,411 @@ pub fn existence_intervals(links: &mut LinkIterator,
     intervals
 }
 
-pub fn largest_boxe(links: &mut LinkIterator, nodes: &Vec<Node>, delta: Time)
+/// Largest box of the delta-existence trace of the nodes, see `largest_boxe_from_trace`.
+pub fn largest_boxe(links: &mut LinkIterator, nodes: &[Node], delta: Time)
                          -> (Time, Time, Vec<Node>) {
-    let trace: Vec<(Time, Vec<bool>)> = delta_existence(links, nodes, delta);
+    largest_boxe_from_trace(&delta_existence(links, nodes, delta))
+}
+
+/// Area of a box `(start, stop, nodes)` of a delta-existence trace, a box of a single timestamp being 1 wide.
+fn box_area(&(start, stop, ref nodes): &(Time, Time, Vec<Node>)) -> Time {
+    (stop - start).max(1) * nodes.len() as Time
+}
+
+/// Find the largest box of a delta-existence trace, with the area of `top_boxes`.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::largest_boxe_from_trace;
+/// let trace = vec![(10, vec![true, true, true]), (9, vec![true, false, false])];
+/// assert_eq!(largest_boxe_from_trace(&trace), (10, 10, vec![0, 1, 2]));
+/// ```
+pub fn largest_boxe_from_trace(trace: &[(Time, Vec<bool>)]) -> (Time, Time, Vec<Node>) {
     let mut stack: Vec<(Time, Time, Vec<bool>)> = Vec::new();
     let mut max_score: Time = 0;
     let mut max: (Time, Time, Vec<Node>) = (0, 0, Vec::new());
-    for (tcurr, vec) in trace {
+    for &(tcurr, ref vec) in trace {
         let size = stack.len();
         for i in 0..size {
             let (_, tstop, ref vec2) = stack[i].clone();
-            stack[i] = (tcurr, tstop, and(&vec,&vec2));
+            stack[i] = (tcurr, tstop, and(vec, vec2));
         }
-        stack.push((tcurr-1, tcurr, vec));
-        for i in 0..size {
-            let (tstart, tstop, ref vec) = stack[i];
-            let v2 = boolvec_to_set(&vec);
-            let score = (tstop - tstart)*(v2.len() as Time);
+        stack.push((tcurr, tcurr, vec.clone()));
+        for &(tstart, tstop, ref vec) in &stack {
+            let candidate = (tstart, tstop, boolvec_to_set(vec));
+            let score = box_area(&candidate);
             if score > max_score {
                 max_score = score;
-                max = (tstart.clone(), tstop.clone(), v2.clone());
+                max = candidate;
             }
         }
     }
     max
 }
 
+/// Enumerate the maximal boxes of a delta-existence trace with at least `min_nodes` nodes.
+///
+/// A box `(start, stop, nodes)` is maximal when no node can be added to it and its time
+/// interval can not be extended without losing a node. For each end of the box, the
+/// candidate node sets form a chain of nested sets, so there are at most as many maximal
+/// boxes ending at a given time as there are nodes.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::maximal_boxes;
+/// let trace = vec![
+///     (10, vec![true, true, false]),
+///     (8, vec![true, true, true]),
+///     (5, vec![false, true, true]),
+///     (3, vec![true, true, true]),
+/// ];
+/// assert_eq!(maximal_boxes(&trace, 2), vec![
+///     (8, 10, vec![0, 1]),
+///     (8, 8, vec![0, 1, 2]),
+///     (3, 8, vec![1, 2]),
+///     (3, 3, vec![0, 1, 2]),
+/// ]);
+/// ```
+pub fn maximal_boxes(trace: &[(Time, Vec<bool>)], min_nodes: usize) -> Vec<(Time, Time, Vec<Node>)> {
+    let mut boxes: Vec<(Time, Time, Vec<Node>)> = Vec::new();
+    nested_chain_sweep(trace, |j, tcurr, chain| {
+        let following = trace.get(j + 1).map(|(_, col)| BitSet::from_bools(col));
+        for &(tstop, ref set) in chain {
+            let extensible = following.as_ref().is_some_and(|col| {
+                let mut kept = set.clone();
+                kept.intersect_with(col);
+                kept == *set
+            });
+            if !extensible && set.len() >= min_nodes {
+                boxes.push((tcurr, tstop, set.to_set()));
+            }
+        }
+    });
+    boxes
+}
+
+/// Sweep the columns of an existence trace, calling `visit(j, tcurr, chain)` on each column.
+///
+/// `chain` holds the distinct non empty sets of nodes existing in every column from a previous
+/// one to the current one `(j, tcurr)`, with the time of this first column, by increasing sets.
+/// The sets are nested, so the chain is never longer than the number of nodes.
+fn nested_chain_sweep<'a, I, F>(columns: I, mut visit: F)
+    where I: IntoIterator<Item=&'a (Time, Vec<bool>)>, F: FnMut(usize, Time, &[(Time, BitSet)]) {
+    let mut chain: Vec<(Time, BitSet)> = Vec::new();
+    for (j, &(tcurr, ref col)) in columns.into_iter().enumerate() {
+        let col = BitSet::from_bools(col);
+        let mut next: Vec<(Time, BitSet)> = Vec::with_capacity(chain.len() + 1);
+        for (tstop, mut set) in chain.drain(..).chain(Some((tcurr, col.clone()))) {
+            set.intersect_with(&col);
+            let known = next.last().is_some_and(|(_, last)| *last == set);
+            if !known && !set.is_empty() { next.push((tstop, set)); }
+        }
+        chain = next;
+        visit(j, tcurr, &chain);
+    }
+}
+
+/// Return the `k` largest maximal boxes of a delta-existence trace by decreasing area.
+///
+/// A box `(start, stop, nodes)` has area `(stop - start) x |nodes|`, a box of a single timestamp
+/// being 1 wide, as in `largest_boxe_from_trace`.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::top_boxes;
+/// let trace = vec![
+///     (10, vec![true, true, false]),
+///     (8, vec![true, true, true]),
+///     (5, vec![false, true, true]),
+///     (3, vec![true, true, true]),
+/// ];
+/// assert_eq!(top_boxes(&trace, 2, 1), vec![(3, 8, vec![1, 2]), (3, 10, vec![1])]);
+/// let trace = vec![(10, vec![true, true, true]), (9, vec![true, false, false])];
+/// assert_eq!(top_boxes(&trace, 2, 1), vec![(10, 10, vec![0, 1, 2]), (9, 10, vec![0])]);
+/// ```
+pub fn top_boxes(trace: &[(Time, Vec<bool>)], k: usize, min_nodes: usize) -> Vec<(Time, Time, Vec<Node>)> {
+    let mut boxes = maximal_boxes(trace, min_nodes);
+    boxes.sort_by(|b1, b2| box_area(b2).cmp(&box_area(b1)).then_with(|| b2.1.cmp(&b1.1)));
+    boxes.truncate(k);
+    boxes
+}
+
+/// Mean area of the boxes drawn by existence intervals, as printed by `calc exist cut`.
+///
+/// An interval `(start, stop, nodes)` spans from `stop` to `start + delta + 1`.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::mean_box_size;
+/// let intervals = vec![(10, 8, vec![0, 1]), (7, 5, vec![1])];
+/// assert_eq!(mean_box_size(&intervals, 1), (8. + 4.) / 2.);
+/// assert_eq!(mean_box_size(&Vec::new(), 1), 0.);
+/// ```
+pub fn mean_box_size(intervals: &[(Time, Time, Vec<Node>)], delta: Time) -> f64 {
+    if intervals.is_empty() { return 0.; }
+    let total: f64 = intervals.iter()
+        .map(|&(start, stop, ref set)| ((start + delta + 1 - stop) * set.len() as Time) as f64)
+        .sum();
+    total / intervals.len() as f64
+}
+
+// ////////////////////////////
+//          TRIANGLES
+// ////////////////////////////
+
+/// Triangles counted at a time and clustering coefficients of the nodes.
+pub type Triangles = (Time, u64, Vec<(Node, f64)>);
+
+/// Count the delta-triangles of the linkstream and compute the delta-clustering of its nodes over time.
+///
+/// A delta-triangle is a triple of links on the three pairs of three nodes, the last one at most
+/// `delta` after the first one. The stream is swept by decreasing time, each triangle being counted
+/// at the time of its earliest link.
+///
+/// For each time `t` of the stream, gives `(t, triangles, clustering)` where `triangles` is the
+/// number of triangles counted at `t` and `clustering` the clustering coefficient of the nodes
+/// in the graph of the links within `[t, t + delta]`: the ratio of the pairs of their neighbours
+/// which are linked. Only nodes with at least two neighbours have a coefficient.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::delta_triangles;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 10},
+///     Link {node1: 1, node2: 2, time: 9},
+///     Link {node1: 0, node2: 2, time: 8},
+///     Link {node1: 0, node2: 1, time: 3},
+///     Link {node1: 1, node2: 2, time: 2},
+///     Link {node1: 0, node2: 2, time: 1},
+/// ];
+/// let triangles = delta_triangles(&mut links.into_iter(), 2);
+/// assert_eq!(triangles, vec![
+///     (10, 0, vec![]),
+///     (9, 0, vec![(1, 0.)]),
+///     (8, 1, vec![(0, 1.), (1, 1.), (2, 1.)]),
+///     (3, 0, vec![]),
+///     (2, 0, vec![(1, 0.)]),
+///     (1, 1, vec![(0, 1.), (1, 1.), (2, 1.)]),
+/// ]);
+/// ```
+pub fn delta_triangles(links: &mut LinkIterator, delta: Time) -> Vec<Triangles> {
+    let max_val: Time = Time::MAX;
+    let mut resultat: Vec<Triangles> = Vec::new();
+    let mut window: VecDeque<Link> = VecDeque::new();
+    let mut neighbours: BTreeMap<Node, HashMap<Node, usize>> = BTreeMap::new();
+    let mut curr: Time = max_val;
+    let mut triangles: u64 = 0;
+    for link in links {
+        if link.time != curr && curr != max_val {
+            resultat.push((curr, triangles, clustering(&neighbours)));
+            triangles = 0;
+        }
+        curr = link.time;
+        triangles += maj_triangles(link, delta, &mut window, &mut neighbours);
+    }
+    if curr != max_val {
+        resultat.push((curr, triangles, clustering(&neighbours)));
+    }
+    resultat
+}
+
+// Update the window with a new link, returning the number of triangles it closes.
+fn maj_triangles(link: Link,
+                 delta: Time,
+                 window: &mut VecDeque<Link>,
+                 neighbours: &mut BTreeMap<Node, HashMap<Node, usize>>) -> u64 {
+    shift_window(link.time.saturating_add(delta), window, neighbours);
+    let (u, v) = (link.node1, link.node2);
+    if u == v { return 0; }
+    let mut triangles: u64 = 0;
+    if let (Some(nu), Some(nv)) = (neighbours.get(&u), neighbours.get(&v)) {
+        let (small, large) = if nu.len() < nv.len() { (nu, nv) } else { (nv, nu) };
+        for (w, count) in small {
+            if let Some(other) = large.get(w) { triangles += (count * other) as u64; }
+        }
+    }
+    push_window(link, window, neighbours);
+    triangles
+}
+
+// Remove from the window the links after `bound`.
+fn shift_window(bound: Time,
+                window: &mut VecDeque<Link>,
+                neighbours: &mut BTreeMap<Node, HashMap<Node, usize>>) {
+    while window.front().is_some_and(|other| other.time > bound) {
+        let other = window.pop_front().unwrap();
+        for (u, v) in [(other.node1, other.node2), (other.node2, other.node1)] {
+            let counts = neighbours.get_mut(&u).unwrap();
+            let count = counts.get_mut(&v).unwrap();
+            *count -= 1;
+            if *count == 0 { counts.remove(&v); }
+            if counts.is_empty() { neighbours.remove(&u); }
+        }
+    }
+}
+
+// Add a link to the window, self loops excepted.
+fn push_window(link: Link,
+               window: &mut VecDeque<Link>,
+               neighbours: &mut BTreeMap<Node, HashMap<Node, usize>>) {
+    if link.node1 == link.node2 { return; }
+    *neighbours.entry(link.node1).or_default().entry(link.node2).or_insert(0) += 1;
+    *neighbours.entry(link.node2).or_default().entry(link.node1).or_insert(0) += 1;
+    window.push_back(link);
+}
+
+// Clustering coefficient of the nodes with at least two neighbours.
+fn clustering(neighbours: &BTreeMap<Node, HashMap<Node, usize>>) -> Vec<(Node, f64)> {
+    let mut result: Vec<(Node, f64)> = Vec::new();
+    for (&u, counts) in neighbours {
+        let degree = counts.len();
+        if degree < 2 { continue; }
+        let nodes: Vec<Node> = counts.keys().cloned().collect();
+        let mut linked = 0;
+        for (i, v) in nodes.iter().enumerate() {
+            linked += nodes[i + 1..].iter().filter(|w| neighbours[v].contains_key(w)).count();
+        }
+        result.push((u, linked as f64 / (degree * (degree - 1) / 2) as f64));
+    }
+    result
+}
+
+// ////////////////////////////
+//            CORES
+// ////////////////////////////
+
+/// Compute the (k, delta)-core decomposition of the linkstream.
+///
+/// At each time `t` of the stream, a node is in the (k, delta)-core when it has at least `k`
+/// neighbours in the (k, delta)-core within `[t, t + delta]`, that is when it is in the k-core of
+/// the graph of the links within `[t, t + delta]`. The core number of a node at time `t` is the
+/// greatest such `k`.
+///
+/// Gives the intervals `(start, stop, node, core)` during which `node` has the core number `core`,
+/// from the first time of the stream to the last one, nodes without link in the window having none.
+/// Intervals follow the stream, by decreasing `stop` then `start`, and by increasing node.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::delta_cores;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 10},
+///     Link {node1: 1, node2: 2, time: 9},
+///     Link {node1: 0, node2: 2, time: 8},
+///     Link {node1: 2, node2: 3, time: 8},
+/// ];
+/// assert_eq!(delta_cores(&mut links.into_iter(), 2), vec![
+///     (9, 10, 0, 1),
+///     (9, 10, 1, 1),
+///     (9, 9, 2, 1),
+///     (8, 8, 0, 2),
+///     (8, 8, 1, 2),
+///     (8, 8, 2, 2),
+///     (8, 8, 3, 1),
+/// ]);
+/// ```
+pub fn delta_cores(links: &mut LinkIterator, delta: Time) -> Vec<(Time, Time, Node, usize)> {
+    let max_val: Time = Time::MAX;
+    let mut resultat: Vec<(Time, Time, Node, usize)> = Vec::new();
+    let mut window: VecDeque<Link> = VecDeque::new();
+    let mut neighbours: BTreeMap<Node, HashMap<Node, usize>> = BTreeMap::new();
+    // node -> (start, stop, core) of its current interval
+    let mut current: BTreeMap<Node, (Time, Time, usize)> = BTreeMap::new();
+    let mut curr: Time = max_val;
+    for link in links {
+        if link.time != curr && curr != max_val {
+            maj_cores(curr, &neighbours, &mut current, &mut resultat);
+        }
+        curr = link.time;
+        shift_window(curr.saturating_add(delta), &mut window, &mut neighbours);
+        push_window(link, &mut window, &mut neighbours);
+    }
+    if curr != max_val {
+        maj_cores(curr, &neighbours, &mut current, &mut resultat);
+    }
+    resultat.extend(current.into_iter().map(|(node, (start, stop, core))| (start, stop, node, core)));
+    resultat.sort_by(|a, b| b.1.cmp(&a.1).then(b.0.cmp(&a.0)).then(a.2.cmp(&b.2)));
+    resultat
+}
+
+// Extend the current intervals with the core numbers at time `t`, closing the ones which change.
+fn maj_cores(t: Time,
+             neighbours: &BTreeMap<Node, HashMap<Node, usize>>,
+             current: &mut BTreeMap<Node, (Time, Time, usize)>,
+             resultat: &mut Vec<(Time, Time, Node, usize)>) {
+    let cores = core_numbers(neighbours);
+    let nodes: Vec<Node> = current.keys().chain(cores.keys()).cloned().collect::<BTreeSet<Node>>().into_iter().collect();
+    for node in nodes {
+        match (current.get(&node).cloned(), cores.get(&node)) {
+            (Some((_, stop, core)), Some(&new)) if core == new => { current.insert(node, (t, stop, core)); },
+            (previous, new) => {
+                if let Some((start, stop, core)) = previous {
+                    resultat.push((start, stop, node, core));
+                    current.remove(&node);
+                }
+                if let Some(&new) = new { current.insert(node, (t, t, new)); }
+            },
+        }
+    }
+}
+
+/// Core numbers of the nodes of a graph given by its adjacency, by repeatedly removing a node of minimal degree.
+///
+/// Nodes are kept in buckets by degree (Batagelj and Zaversnik), so that the decomposition is linear
+/// in the size of the graph.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::core_numbers;
+/// # use std::collections::{BTreeMap, HashMap};
+/// let mut graph: BTreeMap<usize, HashMap<usize, usize>> = BTreeMap::new();
+/// for &(u, v) in [(0, 1), (1, 2), (0, 2), (2, 3)].iter() {
+///     graph.entry(u).or_default().insert(v, 1);
+///     graph.entry(v).or_default().insert(u, 1);
+/// }
+/// let cores: Vec<(usize, usize)> = core_numbers(&graph).into_iter().collect();
+/// assert_eq!(cores, vec![(0, 2), (1, 2), (2, 2), (3, 1)]);
+/// ```
+pub fn core_numbers<V>(neighbours: &BTreeMap<Node, HashMap<Node, V>>) -> BTreeMap<Node, usize> {
+    let nodes: Vec<Node> = neighbours.keys().cloned().collect();
+    let index: HashMap<Node, usize> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+    let mut degrees: Vec<usize> = nodes.iter().map(|node| neighbours[node].len()).collect();
+    let max_degree = degrees.iter().cloned().max().unwrap_or(0);
+    // nodes by increasing degree, bins[d] being the position of the first one of degree d
+    let mut bins: Vec<usize> = vec![0; max_degree + 2];
+    for &degree in &degrees { bins[degree + 1] += 1; }
+    for d in 1..bins.len() { bins[d] += bins[d - 1]; }
+    let mut order: Vec<usize> = vec![0; nodes.len()];
+    let mut position: Vec<usize> = vec![0; nodes.len()];
+    {
+        let mut next = bins.clone();
+        for (i, &degree) in degrees.iter().enumerate() {
+            position[i] = next[degree];
+            order[next[degree]] = i;
+            next[degree] += 1;
+        }
+    }
+    for k in 0..nodes.len() {
+        let i = order[k];
+        for other in neighbours[&nodes[i]].keys() {
+            let j = match index.get(other) { Some(&j) => j, None => continue };
+            if degrees[j] > degrees[i] {
+                // move j at the start of its bin, then shift the bin so that j falls in the one below
+                let first = bins[degrees[j]];
+                let w = order[first];
+                order.swap(position[j], first);
+                position[w] = position[j];
+                position[j] = first;
+                bins[degrees[j]] += 1;
+                degrees[j] -= 1;
+            }
+        }
+    }
+    nodes.into_iter().zip(degrees).collect()
+}
+
 // ////////////////////////////
 //         SMALL ALGOS
 // ////////////////////////////
@@ -525,12 +1107,12 @@ pub fn count_first_and_last_apparition(links: &mut LinkIterator, size: usize) ->
 
 /// Count the number of different nodes the stdin linkstream has and the number of links
 pub fn count_nodes_and_links(links: &mut LinkIterator) -> (usize, usize) {
-    let mut seens: Vec<Node> = Vec::new();
+    let mut seens: HashSet<Node> = HashSet::new();
     let mut count = 0;
     for link in links {
-        count = count + 1;
-        if ! seens.contains(&link.node1) { seens.push(link.node1); }
-        if ! seens.contains(&link.node2) { seens.push(link.node2); }
+        count += 1;
+        seens.insert(link.node1);
+        seens.insert(link.node2);
     }
     (seens.len(), count)
 }
@@ -605,12 +1187,54 @@ pub fn largest_rectangle(tab: &Vec<(Time, Vec<bool>)>, width: usize, height: usi
     (best_ll, best_ur)
 }
 
-// pub fn largest_unordered_rectangle(tab: &Vec<(Time, Vec<bool>)>, width: usize, height: usize) -> (Vec<bool>, Time, Time) {
-//     let mut best_set: Vec<bool> = Vec::with_capacity(height);
-//     let mut best_start: Time = 0;
-//     let mut best_stop: Time = 0;
-//     let cache: Vec<(Vec<bool>, Time, Time)> = Vec::new();
-// }
+/// Find the largest rectangle of the existence matrix `tab` whatever the order of the nodes.
+///
+/// Returns the node set, the start and the stop of the rectangle. Without `min_duration` the
+/// rectangle maximizes `|nodes| x (stop - start)`. With a `min_duration` it is the rectangle
+/// with the most nodes among those lasting at least `min_duration`, the longest one in case of tie.
+///
+/// The node sets of the rectangles ending at each column are given by the same nested chain sweep
+/// as `maximal_boxes`, so the sweep costs `width x height` set intersections.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::largest_unordered_rectangle;
+/// let tab = vec![
+///     (10, vec![true, true, false]),
+///     (8, vec![true, true, true]),
+///     (5, vec![false, true, true]),
+///     (3, vec![true, true, true]),
+/// ];
+/// assert_eq!(largest_unordered_rectangle(&tab, 4, 3, None), (vec![false, true, true], 3, 8));
+/// assert_eq!(largest_unordered_rectangle(&tab, 5, 3, None), (vec![false, true, true], 3, 8));
+/// assert_eq!(largest_unordered_rectangle(&tab, 4, 3, Some(6)), (vec![false, true, false], 3, 10));
+/// assert_eq!(largest_unordered_rectangle(&tab, 4, 3, Some(8)), (vec![false, false, false], 0, 0));
+/// ```
+pub fn largest_unordered_rectangle(tab: &[(Time, Vec<bool>)], width: usize, height: usize,
+                                   min_duration: Option<Time>) -> (Vec<bool>, Time, Time) {
+    let mut best_set: BitSet = BitSet::new(height);
+    let mut best_start: Time = 0;
+    let mut best_stop: Time = 0;
+    let mut best_score: (Time, Time) = (0, 0);
+    nested_chain_sweep(tab.iter().take(width), |_, tcurr, chain| {
+        for &(tstop, ref set) in chain {
+            let (nodes, duration) = (set.len() as Time, tstop - tcurr);
+            let score = match min_duration {
+                None => (nodes * duration, 0),
+                Some(min) if duration >= min => (nodes, duration),
+                Some(_) => (0, 0),
+            };
+            if score > best_score {
+                best_score = score;
+                best_set = set.clone();
+                best_start = tcurr;
+                best_stop = tstop;
+            }
+        }
+    });
+    (best_set.to_bools(), best_start, best_stop)
+}
+
 /// ```
 /// # use linkstreams::algo::boolvec_to_set;
 /// let v1 = vec![true, false, true, false];
@@ -626,3 +1250,92 @@ pub fn boolvec_to_set(v1: &Vec<bool>) -> Vec<usize> {
     }
     res
 }
+
+// /////////////
+// / TEST ZONE /
+// /////////////
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use rayon::ThreadPoolBuilder;
+    use data::iterators::link_generator::rng_from_seed;
+    use data::iterators::planted_link_generator::PlantedLinkGenerator;
+    use data::iterators::uniform_link_generator::UnifLinkGenerator;
+    use data::sparse_matrix::SparseMatrix;
+
+    // The sweep only copies the rows changed at the previous time, check it against a full copy.
+    fn check_distance_sweep<D: DistMatrix, F: Fn() -> D>(empty: F, links: &[Link]) {
+        let mut sweep = DistanceSweep::new(empty(), empty());
+        let (mut dist, mut p_dist, mut curr) = (empty(), empty(), Time::MAX);
+        let size = dist.size();
+        for &link in links {
+            if link.time != curr {
+                p_dist.copy(&dist);
+                dist.diag(link.time);
+                curr = link.time;
+            }
+            dist.add_link(&p_dist, link.node1, link.node2, link.time);
+            sweep.push(link);
+            for x in 0..size {
+                for y in 0..size { assert_eq!(dist.get(x, y), sweep.dist.get(x, y)); }
+            }
+        }
+    }
+
+    #[test]
+    fn core_numbers_test() {
+        let links: Vec<Link> = UnifLinkGenerator::new(30, 3, 0.1, rng_from_seed(Some(1))).collect();
+        let mut graph: BTreeMap<Node, HashMap<Node, usize>> = BTreeMap::new();
+        for link in links {
+            *graph.entry(link.node1).or_default().entry(link.node2).or_insert(0) += 1;
+            *graph.entry(link.node2).or_default().entry(link.node1).or_insert(0) += 1;
+        }
+        // peel a node of minimal degree at each step
+        let mut degrees: BTreeMap<Node, usize> = graph.iter().map(|(&node, ns)| (node, ns.len())).collect();
+        let mut expected: BTreeMap<Node, usize> = BTreeMap::new();
+        let mut core = 0;
+        while let Some((&node, &degree)) = degrees.iter().min_by_key(|&(_, &degree)| degree) {
+            core = core.max(degree);
+            expected.insert(node, core);
+            degrees.remove(&node);
+            for other in graph[&node].keys() {
+                if let Some(degree) = degrees.get_mut(other) { *degree -= 1; }
+            }
+        }
+        assert!(expected.values().any(|&core| core > 2));
+        assert_eq!(expected, core_numbers(&graph));
+    }
+
+    #[test]
+    fn distance_sweep_test() {
+        let links: Vec<Link> = UnifLinkGenerator::new(15, 300, 0.02, rng_from_seed(Some(1))).collect();
+        assert!(links.windows(2).any(|w| w[0].time == w[1].time));
+        check_distance_sweep(|| Matrix::new(Time::MAX, 15, 15), &links);
+        check_distance_sweep(|| SparseMatrix::new(15), &links);
+    }
+
+    #[test]
+    fn delta_partition_test() {
+        let links: Vec<Link> = PlantedLinkGenerator::new(12, 500, 10, 3, 0.0005, rng_from_seed(Some(1))).collect();
+        let nodes: Vec<Node> = (0..12).collect();
+        let partition = |threads: usize| {
+            let pool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            pool.install(|| delta_partition(&mut links.clone().into_iter(), &nodes, 10, false))
+        };
+        let sequential = partition(1);
+        assert!(sequential.len() > 1);
+        assert!(sequential.windows(2).all(|w| w[0].0 > w[1].0));
+        assert_eq!(sequential, partition(4));
+        // each interval against the components computed on the whole stream
+        for upper in [false, true] {
+            let intervals = existence_intervals(&mut links.clone().into_iter(), &nodes, 10);
+            let expected: Vec<_> = intervals.into_iter().map(|(stop, start, vec)| {
+                let tfilter = move |time: Time| time >= start && time <= stop + 10 + 1;
+                let reach = delta_reachability_graph(&mut links.clone().into_iter(), 10, 12, &|_| true, &tfilter);
+                let comps = if upper { reach_components_upper(&reach, &vec) } else { reach_components_lower(&reach, &vec) };
+                (start, stop, comps)
+            }).collect();
+            assert_eq!(expected, delta_partition(&mut links.clone().into_iter(), &nodes, 10, upper));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use data::link::{Node, Time};
+
+/// Compare found structures with the ground truth, giving `(precision, recall, jaccard)`.
+///
+/// Structures are boxes `(start, stop, nodes)`, seen as sets of cells: the times of `[start, stop]`
+/// times the nodes, or the pairs of nodes if `pairs`. Boxes of `found` must be disjoint, as well as
+/// the boxes of `truth`. Precision is the ratio of found cells which are true, recall the ratio of
+/// true cells which are found and jaccard the ratio of cells both found and true among all of them.
+/// Scores of empty sets are 0.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::benchmark::score;
+/// let truth = vec![(0, 9, vec![0, 1, 2, 3])];
+/// let found = vec![(0, 9, vec![0, 1, 2]), (0, 9, vec![3, 4])];
+/// assert_eq!(score(&found, &truth, false), (40. / 50., 1., 40. / 50.));
+/// assert_eq!(score(&found, &truth, true), (30. / 40., 30. / 60., 30. / 70.));
+/// let found = vec![(5, 14, vec![0, 1, 2, 3])];
+/// assert_eq!(score(&found, &truth, false), (0.5, 0.5, 20. / 60.));
+/// ```
+pub fn score(found: &[(Time, Time, Vec<Node>)], truth: &[(Time, Time, Vec<Node>)], pairs: bool) -> (f64, f64, f64) {
+    let weight = |n: usize| if pairs { (n * n.saturating_sub(1) / 2) as f64 } else { n as f64 };
+    let measure = |boxes: &[(Time, Time, Vec<Node>)]| -> f64 {
+        boxes.iter().map(|&(start, stop, ref nodes)| weight(nodes.len()) * (stop - start + 1) as f64).sum()
+    };
+    let mut common: f64 = 0.;
+    for &(start1, stop1, ref nodes1) in found {
+        for &(start2, stop2, ref nodes2) in truth {
+            let (start, stop) = (start1.max(start2), stop1.min(stop2));
+            if start > stop { continue; }
+            let shared = nodes1.iter().filter(|node| nodes2.contains(node)).count();
+            common += weight(shared) * (stop - start + 1) as f64;
+        }
+    }
+    let (nb_found, nb_truth) = (measure(found), measure(truth));
+    let ratio = |a: f64, b: f64| if b == 0. { 0. } else { a / b };
+    (ratio(common, nb_found), ratio(common, nb_truth), ratio(common, nb_found + nb_truth - common))
+}
+
+/// Planted structures spanning the whole time of the truth, which are its delta-components.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::benchmark::components_truth;
+/// let truth = vec![(0, 9, vec![0, 1]), (2, 5, vec![2, 3]), (0, 9, vec![4, 5])];
+/// assert_eq!(components_truth(&truth), vec![(0, 9, vec![0, 1]), (0, 9, vec![4, 5])]);
+/// ```
+pub fn components_truth(truth: &[(Time, Time, Vec<Node>)]) -> Vec<(Time, Time, Vec<Node>)> {
+    let start = truth.iter().map(|&(start, _, _)| start).min().unwrap_or(0);
+    let stop = truth.iter().map(|&(_, stop, _)| stop).max().unwrap_or(0);
+    truth.iter().filter(|&&(s, e, _)| s == start && e == stop).cloned().collect()
+}
+
+/// Largest rectangle of the delta-existence matrix of planted structures.
+///
+/// A node exists whenever it is in a planted structure, so the largest rectangle is an interval
+/// bounded by planted starts and stops, with all the nodes planted during this interval.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::benchmark::rectangle_truth;
+/// let truth = vec![(0, 9, vec![0, 1]), (2, 5, vec![2, 3, 4, 5, 6])];
+/// assert_eq!(rectangle_truth(&truth), vec![(2, 5, vec![0, 1, 2, 3, 4, 5, 6])]);
+/// let truth = vec![(0, 9, vec![0, 1]), (0, 9, vec![2, 3]), (2, 5, vec![4, 5])];
+/// assert_eq!(rectangle_truth(&truth), vec![(0, 9, vec![0, 1, 2, 3])]);
+/// ```
+pub fn rectangle_truth(truth: &[(Time, Time, Vec<Node>)]) -> Vec<(Time, Time, Vec<Node>)> {
+    let mut best: Option<(Time, Time, Vec<Node>)> = None;
+    for &(start, _, _) in truth {
+        for &(_, stop, _) in truth {
+            if start > stop { continue; }
+            let mut nodes: Vec<Node> = truth.iter()
+                .filter(|&&(s, e, _)| s <= start && stop <= e)
+                .flat_map(|(_, _, nodes)| nodes.iter().cloned())
+                .collect();
+            nodes.sort();
+            let area = |&(start, stop, ref nodes): &(Time, Time, Vec<Node>)| (stop - start) * nodes.len() as Time;
+            let candidate = (start, stop, nodes);
+            if best.as_ref().is_none_or(|best| area(&candidate) > area(best)) {
+                best = Some(candidate);
+            }
+        }
+    }
+    best.into_iter().collect()
+}
+
+/// Boxes of the components of size at least 2 of a delta-partition, given as `(start, stop, components)`.
+///
+/// Each part lasts until the start of the next one, and the last one until `delta` after its stop,
+/// so that the boxes are disjoint and cover the stream.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::benchmark::partition_boxes;
+/// let parts = vec![(6, 8, vec![vec![0, 1]]), (0, 3, vec![vec![0, 1, 2], vec![3]])];
+/// assert_eq!(partition_boxes(&parts, 2), vec![(0, 5, vec![0, 1, 2]), (6, 10, vec![0, 1])]);
+/// ```
+pub fn partition_boxes(parts: &[(Time, Time, Vec<Vec<Node>>)], delta: Time) -> Vec<(Time, Time, Vec<Node>)> {
+    let mut parts: Vec<&(Time, Time, Vec<Vec<Node>>)> = parts.iter().collect();
+    parts.sort_by_key(|&&(start, _, _)| start);
+    let mut boxes: Vec<(Time, Time, Vec<Node>)> = Vec::new();
+    for (i, &&(start, stop, ref comps)) in parts.iter().enumerate() {
+        let end = parts.get(i + 1).map_or(stop + delta, |&&(next, _, _)| next - 1);
+        boxes.extend(comps.iter().filter(|comp| comp.len() > 1).map(|comp| (start, end, comp.clone())));
+    }
+    boxes
+}
+
+/// Parse a `start stop n...` line, as written by `calc exist cut` or `gen planted`.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::benchmark::parse_box;
+/// assert_eq!(parse_box("0 9 1 4"), Some((0, 9, vec![1, 4])));
+/// assert_eq!(parse_box("0 a"), None);
+/// ```
+pub fn parse_box(line: &str) -> Option<(Time, Time, Vec<Node>)> {
+    let mut words = line.split_whitespace();
+    let start = words.next()?.parse::<Time>().ok()?;
+    let stop = words.next()?.parse::<Time>().ok()?;
+    let nodes: Result<Vec<Node>, _> = words.map(|word| word.parse::<Node>()).collect();
+    Some((start, stop, nodes.ok()?))
+}
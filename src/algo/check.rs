@@ -0,0 +1,130 @@
+use data::link::{Link, Node, Time};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// A violation of the preconditions the algorithms rely on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// the line is not a link `n1 n2 t`
+    Malformed(String),
+    /// the time increases after `previous`
+    Unsorted { previous: Time, time: Time },
+    /// a node is linked with itself
+    SelfLoop(Node),
+    /// a node is not lower than the number of nodes given to the algorithms
+    OutOfRange(Node),
+    /// the time is the value used by the algorithms for "never"
+    ReservedTime(Time),
+    /// the greatest time is lower than delta, `tmax - delta` underflows
+    DeltaUnderflow { t_max: Time, delta: Time },
+    /// `tmax + delta + 1` overflows
+    DeltaOverflow { t_max: Time, delta: Time },
+    /// ids without any link between 0 and the number of nodes
+    MissingIds(Vec<Node>),
+}
+
+impl Violation {
+    /// Command fixing the violation
+    pub fn fix(&self) -> &'static str {
+        match *self {
+            Violation::Malformed(_) => "remove the line or fix its format",
+            Violation::Unsorted { .. } => "sort -s -n -r -k3,3",
+            Violation::SelfLoop(_) => "awk '$1 != $2'",
+            Violation::OutOfRange(_) | Violation::MissingIds(_) => "linkstream rename",
+            Violation::ReservedTime(_) => "shift the times of the stream",
+            Violation::DeltaUnderflow { .. } | Violation::DeltaOverflow { .. } => "use a smaller delta",
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Violation::Malformed(ref line) => write!(f, "malformed link \"{}\"", line),
+            Violation::Unsorted { previous, time } => write!(f, "time {} after time {}, the stream is not sorted by decreasing time", time, previous),
+            Violation::SelfLoop(node) => write!(f, "self-loop on node {}", node),
+            Violation::OutOfRange(node) => write!(f, "node {} is out of range", node),
+            Violation::ReservedTime(time) => write!(f, "time {} is reserved", time),
+            Violation::DeltaUnderflow { t_max, delta } => write!(f, "greatest time {} is lower than delta {}", t_max, delta),
+            Violation::DeltaOverflow { t_max, delta } => write!(f, "greatest time {} plus delta {} overflows", t_max, delta),
+            Violation::MissingIds(ref ids) => {
+                let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+                write!(f, "{} ids without any link : {}", ids.len(), ids.join(" "))
+            }
+        }
+    }
+}
+
+/// Check the lines of a linkstream against the preconditions of the algorithms.
+///
+/// Returns each violation with the number of its line, starting from 1, or `None` when it concerns
+/// the whole stream. Nodes must be lower than `nb_nodes` when given, and every id below it
+/// (or below the greatest id otherwise) must have a link. With `delta`, times are checked
+/// against the overflows of the delta arithmetic.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::check::{check, Violation};
+/// let lines = vec!["0 1 10", "1 1 8", "3 1 9", "0 x 2"];
+/// let violations = check(lines.into_iter().map(String::from), Some(3), Some(20));
+/// assert_eq!(violations, vec![
+///     (Some(2), Violation::SelfLoop(1)),
+///     (Some(3), Violation::Unsorted { previous: 8, time: 9 }),
+///     (Some(3), Violation::OutOfRange(3)),
+///     (Some(4), Violation::Malformed("0 x 2".to_string())),
+///     (None, Violation::MissingIds(vec![2])),
+///     (None, Violation::DeltaUnderflow { t_max: 10, delta: 20 }),
+/// ]);
+/// ```
+pub fn check<I>(lines: I, nb_nodes: Option<usize>, delta: Option<Time>) -> Vec<(Option<usize>, Violation)>
+    where I: Iterator<Item=String> {
+    let mut violations: Vec<(Option<usize>, Violation)> = Vec::new();
+    let mut seens: HashSet<Node> = HashSet::new();
+    let mut previous: Option<Time> = None;
+    let mut t_max: Option<Time> = None;
+    for (i, line) in lines.enumerate() {
+        let number = Some(i + 1);
+        let link = match Link::from_str(&line) {
+            Ok(link) => link,
+            Err(_) => {
+                violations.push((number, Violation::Malformed(line)));
+                continue;
+            }
+        };
+        if let Some(previous) = previous {
+            if link.time > previous {
+                violations.push((number, Violation::Unsorted { previous, time: link.time }));
+            }
+        }
+        previous = Some(link.time);
+        t_max = t_max.max(Some(link.time));
+        if link.time == Time::MAX {
+            violations.push((number, Violation::ReservedTime(link.time)));
+        }
+        if link.node1 == link.node2 {
+            violations.push((number, Violation::SelfLoop(link.node1)));
+        }
+        let nodes = if link.node1 == link.node2 { vec![link.node1] } else { vec![link.node1, link.node2] };
+        for node in nodes {
+            if nb_nodes.is_some_and(|n| node >= n) {
+                violations.push((number, Violation::OutOfRange(node)));
+            }
+            seens.insert(node);
+        }
+    }
+    let size = nb_nodes.unwrap_or_else(|| seens.iter().max().map_or(0, |&max| max + 1));
+    let missing: Vec<Node> = (0..size).filter(|node| !seens.contains(node)).collect();
+    if !missing.is_empty() {
+        violations.push((None, Violation::MissingIds(missing)));
+    }
+    if let (Some(t_max), Some(delta)) = (t_max, delta) {
+        if t_max < delta {
+            violations.push((None, Violation::DeltaUnderflow { t_max, delta }));
+        }
+        if t_max.checked_add(delta).and_then(|t| t.checked_add(1)).is_none() {
+            violations.push((None, Violation::DeltaOverflow { t_max, delta }));
+        }
+    }
+    violations
+}
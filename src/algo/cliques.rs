@@ -0,0 +1,147 @@
+use data::link::{Node, Time};
+use data::iterators::link_iterator::LinkIterator;
+use std::collections::{HashMap, HashSet};
+
+/// Enumerate the maximal delta-cliques of a linkstream.
+///
+/// A delta-clique `(start, stop, nodes)` is a set of nodes and a time interval such that every
+/// pair of nodes interacts at least once in every sub-interval of duration `delta`.
+/// Cliques are computed as in Viard, Latapy and Magnien, *Computing maximal cliques in link streams*:
+/// each link is a clique reduced to an instant, and cliques are grown by adding nodes or
+/// extending their interval until nothing can be added. Maximal cliques are yielded as soon as they are found.
+///
+/// The whole stream is kept in memory, indexed by pair of nodes.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::cliques::DeltaCliques;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 6},
+///     Link {node1: 1, node2: 2, time: 5},
+///     Link {node1: 0, node2: 2, time: 4},
+///     Link {node1: 0, node2: 1, time: 2},
+/// ];
+/// let mut cliques: Vec<_> = DeltaCliques::new(&mut links.into_iter(), 2).collect();
+/// cliques.sort();
+/// assert_eq!(cliques, vec![(2, 4, vec![0, 1]), (2, 6, vec![0, 2]), (3, 6, vec![1, 2]), (4, 6, vec![0, 1, 2])]);
+/// ```
+pub struct DeltaCliques {
+    delta: Time,
+    t_min: Time,
+    t_max: Time,
+    pairs: HashMap<(Node, Node), Vec<Time>>,
+    neighbours: HashMap<Node, Vec<Node>>,
+    stack: Vec<(Vec<Node>, Time, Time)>,
+    seens: HashSet<(Vec<Node>, Time, Time)>,
+}
+
+impl DeltaCliques {
+    /// Read the linkstream and prepare the enumeration of its maximal `delta`-cliques.
+    pub fn new(links: &mut LinkIterator, delta: Time) -> DeltaCliques {
+        let mut cliques = DeltaCliques {
+            delta,
+            t_min: Time::MAX,
+            t_max: 0,
+            pairs: HashMap::new(),
+            neighbours: HashMap::new(),
+            stack: Vec::new(),
+            seens: HashSet::new(),
+        };
+        let mut seeds: Vec<(Vec<Node>, Time, Time)> = Vec::new();
+        for link in links {
+            if link.node1 == link.node2 { continue; }
+            let pair = ordered(link.node1, link.node2);
+            cliques.t_min = cliques.t_min.min(link.time);
+            cliques.t_max = cliques.t_max.max(link.time);
+            let times = cliques.pairs.entry(pair).or_default();
+            if times.is_empty() {
+                cliques.neighbours.entry(pair.0).or_default().push(pair.1);
+                cliques.neighbours.entry(pair.1).or_default().push(pair.0);
+            }
+            times.push(link.time);
+            seeds.push((vec![pair.0, pair.1], link.time, link.time));
+        }
+        for times in cliques.pairs.values_mut() { times.sort(); }
+        for seed in seeds { cliques.push(seed); }
+        cliques
+    }
+
+    fn push(&mut self, clique: (Vec<Node>, Time, Time)) {
+        if !self.seens.contains(&clique) {
+            self.seens.insert(clique.clone());
+            self.stack.push(clique);
+        }
+    }
+
+    // Times of the links between u and v occurring in [start, stop]
+    fn times(&self, u: Node, v: Node, start: Time, stop: Time) -> &[Time] {
+        match self.pairs.get(&ordered(u, v)) {
+            None => &[],
+            Some(times) => {
+                let first = times.partition_point(|&t| t < start);
+                let last = times.partition_point(|&t| t <= stop);
+                &times[first..last]
+            }
+        }
+    }
+
+    // Tells if u and v interact in every delta sub-interval of [start, stop]
+    fn is_dense(&self, u: Node, v: Node, start: Time, stop: Time) -> bool {
+        let times = self.times(u, v, start, stop);
+        match (times.first(), times.last()) {
+            (Some(&first), Some(&last)) =>
+                first - start <= self.delta
+                && stop - last <= self.delta
+                && times.windows(2).all(|w| w[1] - w[0] <= self.delta),
+            _ => false,
+        }
+    }
+}
+
+impl Iterator for DeltaCliques {
+    type Item = (Time, Time, Vec<Node>);
+    fn next(&mut self) -> Option<(Time, Time, Vec<Node>)> {
+        while let Some((nodes, start, stop)) = self.stack.pop() {
+            let mut is_max = true;
+            let candidates: Vec<Node> = self.neighbours[&nodes[0]].iter().cloned()
+                .filter(|v| !nodes.contains(v))
+                .filter(|&v| nodes.iter().all(|&u| self.is_dense(u, v, start, stop)))
+                .collect();
+            for v in candidates {
+                is_max = false;
+                let mut bigger = nodes.clone();
+                bigger.push(v);
+                bigger.sort();
+                self.push((bigger, start, stop));
+            }
+            let mut last_first: Time = 0;
+            let mut first_last: Time = Time::MAX;
+            for (i, &u) in nodes.iter().enumerate() {
+                for &v in &nodes[i + 1..] {
+                    let times = self.times(u, v, start, stop);
+                    last_first = last_first.max(times[0]);
+                    first_last = first_last.min(times[times.len() - 1]);
+                }
+            }
+            let new_start = last_first.saturating_sub(self.delta).max(self.t_min);
+            if new_start != start {
+                is_max = false;
+                self.push((nodes.clone(), new_start, stop));
+            }
+            let new_stop = first_last.saturating_add(self.delta).min(self.t_max);
+            if new_stop != stop {
+                is_max = false;
+                self.push((nodes.clone(), start, new_stop));
+            }
+            if is_max {
+                return Some((start, stop, nodes));
+            }
+        }
+        None
+    }
+}
+
+fn ordered(u: Node, v: Node) -> (Node, Node) {
+    if u < v { (u, v) } else { (v, u) }
+}
@@ -0,0 +1,201 @@
+use data::link::{Node, Time};
+use data::iterators::link_iterator::LinkIterator;
+use data::iterators::snapshot_iterator::SnapshotIter;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Detect communities evolving over time.
+///
+/// The stream is aggregated into snapshots of duration `window` every `step`, as with
+/// `SnapshotIter`, and the communities of each snapshot are found with `louvain`. Communities of
+/// consecutive snapshots are then matched by `CommunityTracker`, so that a community keeps its id
+/// while its Jaccard index with its previous nodes is at least `threshold`.
+///
+/// Gives `(start, stop, community_id, nodes)` for each community of each non-empty snapshot.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::communities::temporal_communities;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 9},
+///     Link {node1: 2, node2: 3, time: 8},
+///     Link {node1: 0, node2: 1, time: 3},
+///     Link {node1: 2, node2: 3, time: 2},
+///     Link {node1: 1, node2: 2, time: 1},
+/// ];
+/// assert_eq!(temporal_communities(&mut links.into_iter(), 5, 5, 0.5), vec![
+///     (5, 10, 0, vec![0, 1]),
+///     (5, 10, 1, vec![2, 3]),
+///     (0, 5, 0, vec![0, 1]),
+///     (0, 5, 1, vec![2, 3]),
+/// ]);
+/// ```
+pub fn temporal_communities(links: &mut LinkIterator, window: Time, step: Time, threshold: f64)
+                            -> Vec<(Time, Time, usize, Vec<Node>)> {
+    let mut tracker = CommunityTracker::new(threshold);
+    let mut result: Vec<(Time, Time, usize, Vec<Node>)> = Vec::new();
+    for snapshot in SnapshotIter::new(links, window, step) {
+        if snapshot.edges.is_empty() { continue; }
+        for (id, nodes) in tracker.matching(louvain(&snapshot.edges)) {
+            result.push((snapshot.start, snapshot.stop, id, nodes));
+        }
+    }
+    result
+}
+
+/// Give stable ids to communities found at successive times.
+///
+/// Each community is matched with the previous community of greatest Jaccard index, pairs being
+/// matched greedily by decreasing index, and takes its id when the index is at least the
+/// threshold. Unmatched communities get new ids.
+pub struct CommunityTracker {
+    threshold: f64,
+    next_id: usize,
+    previous: Vec<(usize, Vec<Node>)>,
+}
+
+impl CommunityTracker {
+    /// Create a tracker matching communities with a Jaccard index of at least `threshold`.
+    pub fn new(threshold: f64) -> CommunityTracker {
+        CommunityTracker { threshold, next_id: 0, previous: Vec::new() }
+    }
+
+    /// Give an id to each of the `communities`, sorted by id.
+    pub fn matching(&mut self, communities: Vec<Vec<Node>>) -> Vec<(usize, Vec<Node>)> {
+        let mut pairs: Vec<(f64, usize, usize)> = Vec::new();
+        for (i, community) in communities.iter().enumerate() {
+            for (j, (_, previous)) in self.previous.iter().enumerate() {
+                let index = jaccard(community, previous);
+                if index > 0. && index >= self.threshold { pairs.push((index, i, j)); }
+            }
+        }
+        pairs.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap().then((a.1, a.2).cmp(&(b.1, b.2))));
+        let mut ids: Vec<Option<usize>> = vec![None; communities.len()];
+        let mut taken: Vec<bool> = vec![false; self.previous.len()];
+        for (_, i, j) in pairs {
+            if ids[i].is_none() && !taken[j] {
+                ids[i] = Some(self.previous[j].0);
+                taken[j] = true;
+            }
+        }
+        let mut result: Vec<(usize, Vec<Node>)> = Vec::with_capacity(communities.len());
+        for (id, community) in ids.into_iter().zip(communities) {
+            let id = id.unwrap_or_else(|| { self.next_id += 1; self.next_id - 1 });
+            result.push((id, community));
+        }
+        result.sort();
+        self.previous = result.clone();
+        result
+    }
+}
+
+/// Jaccard index of two sorted sets of nodes.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::communities::jaccard;
+/// assert_eq!(jaccard(&[0, 1, 2], &[1, 2, 3]), 0.5);
+/// assert_eq!(jaccard(&[], &[]), 0.);
+/// ```
+pub fn jaccard(set1: &[Node], set2: &[Node]) -> f64 {
+    let (mut i, mut j, mut common) = (0, 0, 0);
+    while i < set1.len() && j < set2.len() {
+        if set1[i] == set2[j] { common += 1; i += 1; j += 1; }
+        else if set1[i] < set2[j] { i += 1; }
+        else { j += 1; }
+    }
+    let union = set1.len() + set2.len() - common;
+    if union == 0 { 0. } else { common as f64 / union as f64 }
+}
+
+/// Communities of a weighted graph with the Louvain method, by increasing smallest node.
+///
+/// Nodes are moved to the neighbouring community of best modularity gain, in increasing order
+/// and until no move improves the modularity, then communities are merged into nodes and the
+/// process is repeated. Ties keep the current community, so the result is deterministic.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::communities::louvain;
+/// # use std::collections::BTreeMap;
+/// let mut edges: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+/// for &pair in [(0, 1), (0, 2), (1, 2), (3, 4), (3, 5), (4, 5), (2, 3)].iter() {
+///     edges.insert(pair, 1);
+/// }
+/// assert_eq!(louvain(&edges), vec![vec![0, 1, 2], vec![3, 4, 5]]);
+/// ```
+pub fn louvain(edges: &BTreeMap<(Node, Node), usize>) -> Vec<Vec<Node>> {
+    let nodes: Vec<Node> = edges.keys().flat_map(|&(u, v)| vec![u, v]).collect::<BTreeSet<Node>>().into_iter().collect();
+    let index = |node: Node| nodes.binary_search(&node).unwrap();
+    let mut graph: Vec<(usize, usize, f64)> = edges.iter()
+        .map(|(&(u, v), &weight)| (index(u), index(v), weight as f64))
+        .collect();
+    let mut size = nodes.len();
+    // community of each original node
+    let mut membership: Vec<usize> = (0..size).collect();
+    loop {
+        let (community, nb_communities) = one_level(&graph, size);
+        if nb_communities == size { break; }
+        for member in membership.iter_mut() { *member = community[*member]; }
+        let mut weights: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for &(u, v, weight) in &graph {
+            let (cu, cv) = (community[u], community[v]);
+            *weights.entry((cu.min(cv), cu.max(cv))).or_insert(0.) += weight;
+        }
+        graph = weights.into_iter().map(|((u, v), weight)| (u, v, weight)).collect();
+        size = nb_communities;
+    }
+    let mut communities: Vec<Vec<Node>> = vec![Vec::new(); size];
+    for (i, &member) in membership.iter().enumerate() {
+        communities[member].push(nodes[i]);
+    }
+    communities.sort();
+    communities
+}
+
+// Move nodes between communities until no move improves the modularity.
+// Return the community of each node, numbered by order of first node, and their number.
+fn one_level(graph: &[(usize, usize, f64)], size: usize) -> (Vec<usize>, usize) {
+    let mut neighbours: Vec<Vec<(usize, f64)>> = vec![Vec::new(); size];
+    let mut degrees: Vec<f64> = vec![0.; size];
+    for &(u, v, weight) in graph {
+        degrees[u] += weight;
+        degrees[v] += weight;
+        if u != v {
+            neighbours[u].push((v, weight));
+            neighbours[v].push((u, weight));
+        }
+    }
+    let total: f64 = degrees.iter().sum();
+    let mut community: Vec<usize> = (0..size).collect();
+    let mut totals: Vec<f64> = degrees.clone();
+    let mut moved = true;
+    while moved {
+        moved = false;
+        for u in 0..size {
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            for &(v, weight) in &neighbours[u] {
+                *links.entry(community[v]).or_insert(0.) += weight;
+            }
+            let own = community[u];
+            totals[own] -= degrees[u];
+            let gain = |c: usize, weight: f64| weight - totals[c] * degrees[u] / total;
+            let mut best = (own, gain(own, links.get(&own).cloned().unwrap_or(0.)));
+            for (&c, &weight) in &links {
+                let g = gain(c, weight);
+                if g > best.1 + 1e-12 { best = (c, g); }
+            }
+            totals[best.0] += degrees[u];
+            if best.0 != own {
+                community[u] = best.0;
+                moved = true;
+            }
+        }
+    }
+    let mut renumber: BTreeMap<usize, usize> = BTreeMap::new();
+    for c in community.iter_mut() {
+        let next = renumber.len();
+        *c = *renumber.entry(*c).or_insert(next);
+    }
+    (community, renumber.len())
+}
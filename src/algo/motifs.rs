@@ -0,0 +1,163 @@
+use data::link::{Link, Node, Time};
+use data::iterators::link_iterator::LinkIterator;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A motif: its ordered edges, nodes being numbered by order of first appearance.
+pub type Motif = Vec<(usize, usize)>;
+
+/// Count the delta-temporal motifs of a linkstream.
+///
+/// As in Paranjape, Benson and Leskovec, *Motifs in temporal networks*, a delta-temporal motif
+/// instance is a sequence of 2 or 3 links, on 2 or 3 nodes and connected, whose first and last
+/// links are at most `delta` apart. Links at the same time are ordered as in the stream, read from
+/// the oldest to the newest. Self loops are ignored.
+///
+/// In `directed` streams, a link goes from `node1` to `node2` and motifs keep the direction of
+/// their edges. Otherwise edges are written `(low, high)` and the motif is the smallest of the
+/// two numberings given by the endpoints of its first edge.
+///
+/// Motifs are counted as in the paper rather than enumerated: the links of every set of 2 or 3
+/// nodes are swept in time order, counting within a sliding window of duration `delta` the
+/// sequences of 1, 2 and 3 of their edges. The whole stream is kept in memory, indexed by pair of nodes.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::motifs::temporal_motifs;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 2, node2: 0, time: 3},
+///     Link {node1: 1, node2: 2, time: 2},
+///     Link {node1: 0, node2: 1, time: 1},
+/// ];
+/// let motifs = temporal_motifs(&mut links.clone().into_iter(), 2, true);
+/// assert_eq!(motifs.get(&vec![(0, 1), (1, 2), (2, 0)]), Some(&1));
+/// assert_eq!(motifs.get(&vec![(0, 1), (1, 2)]), Some(&2));
+/// assert_eq!(motifs.get(&vec![(0, 1), (2, 0)]), Some(&1));
+/// assert_eq!(motifs.len(), 3);
+/// let motifs = temporal_motifs(&mut links.into_iter(), 1, false);
+/// assert_eq!(motifs.get(&vec![(0, 1), (0, 2)]), Some(&2));
+/// assert_eq!(motifs.len(), 1);
+/// ```
+pub fn temporal_motifs(links: &mut LinkIterator, delta: Time, directed: bool) -> BTreeMap<Motif, u64> {
+    let mut stream: Vec<Link> = links.filter(|link| link.node1 != link.node2).collect();
+    stream.reverse();
+    let mut pairs: BTreeMap<(Node, Node), Vec<usize>> = BTreeMap::new();
+    let mut neighbours: BTreeMap<Node, BTreeSet<Node>> = BTreeMap::new();
+    for (index, link) in stream.iter().enumerate() {
+        let (n1, n2) = (link.node1.min(link.node2), link.node1.max(link.node2));
+        pairs.entry((n1, n2)).or_default().push(index);
+        neighbours.entry(n1).or_default().insert(n2);
+        neighbours.entry(n2).or_default().insert(n1);
+    }
+    let mut motifs: BTreeMap<Motif, u64> = BTreeMap::new();
+    for (&(n1, n2), indexes) in pairs.iter() {
+        count(&stream, &[n1, n2], indexes, delta, directed, &mut motifs);
+    }
+    // A connected set of 3 nodes n1 < n2 < n3 holds a link of n1, so n2 and n3 are neighbours
+    // of n1 or of one of its neighbours.
+    let empty: Vec<usize> = Vec::new();
+    for (&n1, nodes) in neighbours.iter() {
+        let mut candidates: BTreeSet<Node> = BTreeSet::new();
+        for &n2 in nodes.range(n1 + 1..) {
+            candidates.insert(n2);
+            candidates.extend(neighbours[&n2].range(n1 + 1..));
+        }
+        let candidates: Vec<Node> = candidates.into_iter().collect();
+        for (i, &n2) in candidates.iter().enumerate() {
+            for &n3 in candidates[i + 1..].iter() {
+                let linked: Vec<&Vec<usize>> = [(n1, n2), (n1, n3), (n2, n3)].iter()
+                    .map(|pair| pairs.get(pair).unwrap_or(&empty))
+                    .filter(|indexes| !indexes.is_empty())
+                    .collect();
+                if linked.len() < 2 { continue; }
+                let mut merged: Vec<usize> = linked.iter().flat_map(|indexes| indexes.iter().cloned()).collect();
+                merged.sort_unstable();
+                count(&stream, &[n1, n2, n3], &merged, delta, directed, &mut motifs);
+            }
+        }
+    }
+    motifs
+}
+
+/// Count the motifs spanning exactly `nodes` among the links `indexes` of the stream, sorted.
+///
+/// The edges between `nodes` are labelled, then for each link `c1`, `c2` count the sequences of 1
+/// and 2 labels within the last `delta` while `total2`, `total3` count all the sequences of 2 and 3
+/// labels ended so far.
+fn count(stream: &[Link], nodes: &[Node], indexes: &[usize], delta: Time, directed: bool, motifs: &mut BTreeMap<Motif, u64>) {
+    let k = nodes.len();
+    let edges: Vec<(usize, usize)> = (0..k).flat_map(|a| (0..k).map(move |b| (a, b)))
+        .filter(|&(a, b)| if directed { a != b } else { a < b })
+        .collect();
+    let l = edges.len();
+    let label = |index: usize| -> usize {
+        let link = &stream[index];
+        let a = nodes.iter().position(|&node| node == link.node1).unwrap();
+        let b = nodes.iter().position(|&node| node == link.node2).unwrap();
+        let edge = if directed || a < b { (a, b) } else { (b, a) };
+        edges.iter().position(|&other| other == edge).unwrap()
+    };
+    let labels: Vec<usize> = indexes.iter().map(|&index| label(index)).collect();
+    let (mut c1, mut c2) = (vec![0u64; l], vec![0u64; l * l]);
+    let (mut total2, mut total3) = (vec![0u64; l * l], vec![0u64; l * l * l]);
+    let mut begin = 0;
+    for (i, &x) in labels.iter().enumerate() {
+        let time = stream[indexes[i]].time;
+        while stream[indexes[begin]].time.saturating_add(delta) < time {
+            let y = labels[begin];
+            c1[y] -= 1;
+            for b in 0..l { c2[y * l + b] -= c1[b]; }
+            begin += 1;
+        }
+        for ab in 0..l * l { total3[ab * l + x] += c2[ab]; }
+        for a in 0..l {
+            c2[a * l + x] += c1[a];
+            total2[a * l + x] += c1[a];
+        }
+        c1[x] += 1;
+    }
+    let mut add = |sequence: &[usize], number: u64| {
+        if number == 0 { return; }
+        let sequence: Vec<(Node, Node)> = sequence.iter().map(|&x| edges[x]).collect();
+        let mut spanned: Vec<Node> = Vec::new();
+        for &(a, b) in sequence.iter() { extend(&mut spanned, a, b); }
+        if spanned.len() == k {
+            *motifs.entry(motif(&sequence, directed)).or_insert(0) += number;
+        }
+    };
+    for a in 0..l {
+        for b in 0..l {
+            add(&[a, b], total2[a * l + b]);
+            for c in 0..l { add(&[a, b, c], total3[(a * l + b) * l + c]); }
+        }
+    }
+}
+
+fn extend(nodes: &mut Vec<Node>, n1: Node, n2: Node) {
+    for node in [n1, n2] {
+        if !nodes.contains(&node) { nodes.push(node); }
+    }
+}
+
+/// Number the nodes of a sequence of edges by order of first appearance.
+fn motif(edges: &[(Node, Node)], directed: bool) -> Motif {
+    let numbering = |start: [Node; 2]| -> Motif {
+        let mut nodes: Vec<Node> = start.to_vec();
+        edges.iter().map(|&(node1, node2)| {
+            extend(&mut nodes, node1, node2);
+            let n1 = nodes.iter().position(|&node| node == node1).unwrap();
+            let n2 = nodes.iter().position(|&node| node == node2).unwrap();
+            if directed || n1 < n2 { (n1, n2) } else { (n2, n1) }
+        }).collect()
+    };
+    let motif = numbering([edges[0].0, edges[0].1]);
+    if directed { return motif; }
+    motif.min(numbering([edges[0].1, edges[0].0]))
+}
+
+/// Format a motif as its edges `n1>n2`, or `n1-n2` if undirected, separated by commas.
+pub fn motif_to_string(motif: &[(usize, usize)], directed: bool) -> String {
+    let sep = if directed { ">" } else { "-" };
+    let edges: Vec<String> = motif.iter().map(|&(n1, n2)| format!("{}{}{}", n1, sep, n2)).collect();
+    edges.join(",")
+}
@@ -0,0 +1,169 @@
+use data::link::{Link, Node, Time};
+use data::iterators::link_iterator::LinkIterator;
+use rand::Rng;
+use std::collections::VecDeque;
+
+/// Spreading models: infected nodes stay infected (SI), become susceptible again (SIS) or are
+/// removed (SIR) once they have been infected during the recovery duration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Model {
+    SI,
+    SIS,
+    SIR,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Susceptible,
+    Infected,
+    Recovered,
+}
+
+/// Numbers of infected and recovered nodes after each time of a run, and first infection times.
+pub type Run = (Vec<(Time, usize, usize)>, Vec<Option<Time>>);
+
+/// Averaged result of several spreading runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spreading {
+    /// for each time of the stream, the mean numbers of infected and recovered nodes
+    pub curve: Vec<(Time, f64, f64)>,
+    /// for each node, the ratio of runs infecting it and the mean time of its first infection
+    pub infections: Vec<(Node, f64, f64)>,
+}
+
+/// Run a spreading process over the links of `links`, sorted by increasing time.
+///
+/// The `seeds` are infected at the first time of the stream. Then at each link between an infected
+/// node and a susceptible one, the susceptible node is infected with probability `beta`. In SIS and
+/// SIR, an infected node recovers `recovery` after its infection, before the links of that time.
+///
+/// Gives the numbers of infected and recovered nodes after each time of the stream, and the time
+/// of the first infection of each node, if any.
+///
+/// # Example
+/// ```
+/// # extern crate rand;
+/// # extern crate linkstreams;
+/// # use linkstreams::algo::spreading::{spread, Model};
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 1},
+///     Link {node1: 1, node2: 2, time: 3},
+///     Link {node1: 2, node2: 3, time: 6},
+/// ];
+/// let mut rng = rand::thread_rng();
+/// let (curve, infections) = spread(&links, Model::SIR, 1., 4, &[0], &mut rng);
+/// assert_eq!(curve, vec![(1, 2, 0), (3, 3, 0), (6, 2, 2)]);
+/// assert_eq!(infections, vec![Some(1), Some(1), Some(3), Some(6)]);
+/// ```
+pub fn spread<R: Rng>(links: &[Link], model: Model, beta: f64, recovery: Time, seeds: &[Node], rng: &mut R) -> Run {
+    let size = links.iter().map(|link| link.node1.max(link.node2) + 1)
+        .chain(seeds.iter().map(|&node| node + 1))
+        .max().unwrap_or(0);
+    let mut states: Vec<State> = vec![State::Susceptible; size];
+    let mut infections: Vec<Option<Time>> = vec![None; size];
+    let mut curve: Vec<(Time, usize, usize)> = Vec::new();
+    let (mut infected, mut recovered) = (0, 0);
+    // (infection time, node) of the infected nodes, by increasing time
+    let mut pending: VecDeque<(Time, Node)> = VecDeque::new();
+    let start = match links.first() { Some(link) => link.time, None => return (curve, infections) };
+    for &node in seeds {
+        if states[node] == State::Susceptible {
+            states[node] = State::Infected;
+            infections[node] = Some(start);
+            infected += 1;
+            pending.push_back((start, node));
+        }
+    }
+    for (i, link) in links.iter().enumerate() {
+        let t = link.time;
+        if model != Model::SI {
+            while pending.front().is_some_and(|&(time, _)| time.saturating_add(recovery) <= t) {
+                let (_, node) = pending.pop_front().unwrap();
+                infected -= 1;
+                if model == Model::SIR {
+                    states[node] = State::Recovered;
+                    recovered += 1;
+                } else {
+                    states[node] = State::Susceptible;
+                }
+            }
+        }
+        let (s1, s2) = (states[link.node1], states[link.node2]);
+        let target = match (s1, s2) {
+            (State::Infected, State::Susceptible) => Some(link.node2),
+            (State::Susceptible, State::Infected) => Some(link.node1),
+            _ => None,
+        };
+        if let Some(node) = target {
+            if rng.gen_bool(beta) {
+                states[node] = State::Infected;
+                infections[node].get_or_insert(t);
+                infected += 1;
+                pending.push_back((t, node));
+            }
+        }
+        if links.get(i + 1).is_none_or(|next| next.time != t) {
+            curve.push((t, infected, recovered));
+        }
+    }
+    (curve, infections)
+}
+
+/// Average `runs` spreading processes over the linkstream, at least one, drawing them with `rng`.
+///
+/// The stream is expected by decreasing time, and is kept in memory. Mean infection times are
+/// taken over the runs infecting the node, and are NaN if none does.
+///
+/// # Example
+/// ```
+/// # extern crate rand;
+/// # extern crate linkstreams;
+/// # use linkstreams::algo::spreading::{simulate, Model};
+/// # use linkstreams::data::link::Link;
+/// use rand::SeedableRng;
+/// let links = vec![
+///     Link {node1: 1, node2: 2, time: 3},
+///     Link {node1: 0, node2: 1, time: 1},
+/// ];
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+/// let spreading = simulate(&mut links.into_iter(), Model::SI, 1., 0, &[0], 10, &mut rng);
+/// assert_eq!(spreading.curve, vec![(1, 2., 0.), (3, 3., 0.)]);
+/// assert_eq!(spreading.infections, vec![(0, 1., 1.), (1, 1., 1.), (2, 1., 3.)]);
+/// ```
+pub fn simulate<R: Rng>(links: &mut LinkIterator, model: Model, beta: f64, recovery: Time,
+                        seeds: &[Node], runs: usize, rng: &mut R) -> Spreading {
+    assert!(runs > 0, "At least one run is needed");
+    let mut stream: Vec<Link> = links.collect();
+    stream.reverse();
+    let mut curve: Vec<(Time, f64, f64)> = Vec::new();
+    let mut infections: Vec<(Node, f64, f64)> = Vec::new();
+    let mut times: Vec<f64> = Vec::new();
+    for _ in 0..runs {
+        let (run_curve, run_infections) = spread(&stream, model, beta, recovery, seeds, rng);
+        if curve.is_empty() {
+            curve = run_curve.iter().map(|&(t, _, _)| (t, 0., 0.)).collect();
+            infections = (0..run_infections.len()).map(|node| (node, 0., 0.)).collect();
+            times = vec![0.; run_infections.len()];
+        }
+        for (point, &(_, infected, recovered)) in curve.iter_mut().zip(run_curve.iter()) {
+            point.1 += infected as f64;
+            point.2 += recovered as f64;
+        }
+        for (node, infection) in run_infections.iter().enumerate() {
+            if let Some(t) = *infection {
+                infections[node].1 += 1.;
+                times[node] += t as f64;
+            }
+        }
+    }
+    for point in curve.iter_mut() {
+        point.1 /= runs as f64;
+        point.2 /= runs as f64;
+    }
+    for (node, infection) in infections.iter_mut().enumerate() {
+        infection.2 = times[node] / infection.1;
+        infection.1 /= runs as f64;
+    }
+    Spreading { curve, infections }
+}
@@ -0,0 +1,490 @@
+use data::link::{Link, Node, Time};
+use data::iterators::link_iterator::LinkIterator;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use serde::Serialize;
+
+// ////////////////////////////
+//        INTER-CONTACTS
+// ////////////////////////////
+
+/// Inter-event times of each key, in chronological order
+pub type InterEvents<K> = BTreeMap<K, Vec<Time>>;
+
+/// Inter-event times of each pair of nodes and of each node, in chronological order.
+///
+/// Several links at the same time count as a single event.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::stats::inter_event_times;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 10},
+///     Link {node1: 1, node2: 2, time: 10},
+///     Link {node1: 1, node2: 0, time: 7},
+///     Link {node1: 0, node2: 1, time: 1},
+/// ];
+/// let (pairs, nodes) = inter_event_times(&mut links.into_iter());
+/// assert_eq!(pairs[&(0, 1)], vec![6, 3]);
+/// assert_eq!(pairs[&(1, 2)], vec![]);
+/// assert_eq!(nodes[&1], vec![6, 3]);
+/// ```
+pub fn inter_event_times(links: &mut LinkIterator) -> (InterEvents<(Node, Node)>, InterEvents<Node>) {
+    let mut last_pairs: BTreeMap<(Node, Node), Time> = BTreeMap::new();
+    let mut last_nodes: BTreeMap<Node, Time> = BTreeMap::new();
+    let mut pairs: InterEvents<(Node, Node)> = BTreeMap::new();
+    let mut nodes: InterEvents<Node> = BTreeMap::new();
+    for link in links {
+        let (u, v, t) = (link.node1, link.node2, link.time);
+        let pair = if u <= v { (u, v) } else { (v, u) };
+        push_event(&mut last_pairs, &mut pairs, pair, t);
+        push_event(&mut last_nodes, &mut nodes, u, t);
+        if v != u { push_event(&mut last_nodes, &mut nodes, v, t); }
+    }
+    for taus in pairs.values_mut() { taus.reverse(); }
+    for taus in nodes.values_mut() { taus.reverse(); }
+    (pairs, nodes)
+}
+
+// Record an event of `key` at time `t`, the stream being read by decreasing time
+fn push_event<K: Ord + Copy>(lasts: &mut BTreeMap<K, Time>, taus: &mut InterEvents<K>, key: K, t: Time) {
+    let entry = taus.entry(key).or_default();
+    if let Some(&last) = lasts.get(&key) {
+        if last != t { entry.push(last - t); }
+    }
+    lasts.insert(key, t);
+}
+
+fn mean_and_std(values: &[Time]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().map(|&v| v as f64).sum::<f64>() / n;
+    let var = values.iter().map(|&v| (v as f64 - mean) * (v as f64 - mean)).sum::<f64>() / n;
+    (mean, var.sqrt())
+}
+
+/// Mean of inter-event times, NaN if there are none.
+pub fn mean(taus: &[Time]) -> f64 {
+    if taus.is_empty() { return f64::NAN; }
+    mean_and_std(taus).0
+}
+
+/// Burstiness coefficient `B = (sigma - mu) / (sigma + mu)` of inter-event times.
+///
+/// B is -1 for periodic events, 0 for a Poisson process and tends to 1 for bursty events.
+/// It is NaN with less than two inter-event times.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::stats::burstiness;
+/// assert_eq!(burstiness(&vec![5, 5, 5]), -1.);
+/// assert_eq!(burstiness(&vec![1, 3]), (1. - 2.) / (1. + 2.));
+/// assert!(burstiness(&vec![5]).is_nan());
+/// ```
+pub fn burstiness(taus: &[Time]) -> f64 {
+    if taus.len() < 2 { return f64::NAN; }
+    let (mean, std) = mean_and_std(taus);
+    (std - mean) / (std + mean)
+}
+
+/// Memory coefficient of inter-event times: the correlation between consecutive inter-event times.
+///
+/// It is NaN with less than three inter-event times or when they do not vary.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::stats::memory_coefficient;
+/// assert!((memory_coefficient(&vec![1, 2, 3, 4]) - 1.).abs() < 1e-9);
+/// assert!((memory_coefficient(&vec![1, 5, 1, 5]) + 1.).abs() < 1e-9);
+/// assert!(memory_coefficient(&vec![2, 2, 2]).is_nan());
+/// ```
+pub fn memory_coefficient(taus: &[Time]) -> f64 {
+    if taus.len() < 3 { return f64::NAN; }
+    let (firsts, seconds) = (&taus[..taus.len() - 1], &taus[1..]);
+    let (m1, s1) = mean_and_std(firsts);
+    let (m2, s2) = mean_and_std(seconds);
+    if s1 == 0. || s2 == 0. { return f64::NAN; }
+    let cov: f64 = firsts.iter().zip(seconds.iter())
+        .map(|(&t1, &t2)| (t1 as f64 - m1) * (t2 as f64 - m2))
+        .sum();
+    cov / (firsts.len() as f64 * s1 * s2)
+}
+
+/// Histogram of inter-event times with logarithmic bins `[base^k, base^(k+1))`.
+///
+/// Returns the non-empty bins as `(low, high, count)`, `high` being excluded.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::stats::log_histogram;
+/// assert_eq!(log_histogram(&vec![1, 2, 3, 9, 15], 2),
+///            vec![(1, 2, 1), (2, 4, 2), (8, 16, 2)]);
+/// ```
+pub fn log_histogram(taus: &[Time], base: Time) -> Vec<(Time, Time, usize)> {
+    assert!(base > 1, "Logarithmic bins need a base greater than 1");
+    let mut bins: BTreeMap<Time, usize> = BTreeMap::new();
+    for &tau in taus {
+        let mut low: Time = 1;
+        while low.saturating_mul(base) <= tau { low *= base; }
+        *bins.entry(low).or_insert(0) += 1;
+    }
+    bins.into_iter().map(|(low, count)| (low, low.saturating_mul(base), count)).collect()
+}
+
+// ////////////////////////////
+//          ACTIVITY
+// ////////////////////////////
+
+/// Activity of the stream during the time bin `[start, stop)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Activity {
+    pub start: Time,
+    pub stop: Time,
+    /// number of links
+    pub links: usize,
+    /// number of distinct active nodes
+    pub nodes: usize,
+    /// number of distinct linked pairs
+    pub pairs: usize,
+    /// number of nodes appearing for the first time in the stream
+    pub new_nodes: usize,
+}
+
+// Activity of an unfinished bin
+#[derive(Default)]
+struct OpenBin {
+    links: usize,
+    nodes: HashSet<Node>,
+    pairs: HashSet<(Node, Node)>,
+}
+
+/// Cut the stream into time bins of duration `bin` and count the activity of each of them.
+///
+/// The stream is expected by decreasing time: the first bin ends just after the first link and
+/// bins are returned by decreasing time, empty bins included. With `delta`, a node is active in a
+/// bin if it delta-exists at some time of the bin, as in `delta_existence`: it has a link in
+/// `[t, t + delta)` for some `t` of the bin. Links, pairs and new nodes are not smoothed.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::stats::activity;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 9},
+///     Link {node1: 1, node2: 0, time: 8},
+///     Link {node1: 1, node2: 2, time: 5},
+///     Link {node1: 2, node2: 3, time: 0},
+/// ];
+/// let raw: Vec<_> = activity(&mut links.clone().into_iter(), 4, None).iter()
+///     .map(|a| (a.start, a.stop, a.links, a.nodes, a.pairs, a.new_nodes)).collect();
+/// assert_eq!(raw, vec![(6, 10, 2, 2, 1, 1), (2, 6, 1, 2, 1, 1), (0, 2, 1, 2, 1, 2)]);
+/// let smoothed: Vec<_> = activity(&mut links.into_iter(), 4, Some(5)).iter()
+///     .map(|a| a.nodes).collect();
+/// assert_eq!(smoothed, vec![2, 3, 3]);
+/// ```
+pub fn activity(links: &mut LinkIterator, bin: Time, delta: Option<Time>) -> Vec<Activity> {
+    assert!(bin > 0, "Bins must last a positive duration");
+    assert!(delta != Some(0), "Delta must be positive");
+    let mut result: Vec<Activity> = Vec::new();
+    let mut open: VecDeque<OpenBin> = VecDeque::new();
+    let mut firsts: HashMap<Node, Time> = HashMap::new();
+    let mut t_end: Option<Time> = None;
+    for link in links {
+        let end = *t_end.get_or_insert(link.time + 1);
+        let index = |t: Time| ((end - 1 - t) / bin) as usize;
+        let k = index(link.time);
+        let k_last = match delta {
+            None => k,
+            Some(delta) => index((link.time + 1).saturating_sub(delta)),
+        };
+        while result.len() < k {
+            close_bin(&mut result, open.pop_front().unwrap_or_default(), end, bin);
+        }
+        while open.len() <= k_last - k { open.push_back(OpenBin::default()); }
+        let pair = if link.node1 <= link.node2 { (link.node1, link.node2) } else { (link.node2, link.node1) };
+        open[0].links += 1;
+        open[0].pairs.insert(pair);
+        for bin_state in open.iter_mut().take(k_last - k + 1) {
+            bin_state.nodes.insert(link.node1);
+            bin_state.nodes.insert(link.node2);
+        }
+        firsts.insert(link.node1, link.time);
+        firsts.insert(link.node2, link.time);
+    }
+    let end = match t_end { None => return result, Some(end) => end };
+    let last = ((end - 1 - firsts.values().min().unwrap()) / bin) as usize;
+    while result.len() <= last {
+        close_bin(&mut result, open.pop_front().unwrap_or_default(), end, bin);
+    }
+    for &first in firsts.values() {
+        result[((end - 1 - first) / bin) as usize].new_nodes += 1;
+    }
+    result
+}
+
+fn close_bin(result: &mut Vec<Activity>, bin_state: OpenBin, end: Time, bin: Time) {
+    let stop = end - result.len() as Time * bin;
+    result.push(Activity {
+        start: stop.saturating_sub(bin),
+        stop,
+        links: bin_state.links,
+        nodes: bin_state.nodes.len(),
+        pairs: bin_state.pairs.len(),
+        new_nodes: 0,
+    });
+}
+
+// ////////////////////////////
+//          SUMMARY
+// ////////////////////////////
+
+/// Everything worth checking about a stream before using it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    /// number of distinct nodes
+    pub nodes: usize,
+    pub links: usize,
+    /// number of distinct pairs of linked nodes
+    pub pairs: usize,
+    pub t_min: Time,
+    pub t_max: Time,
+    /// the stream is sorted by decreasing time, as expected by the tool
+    pub decreasing: bool,
+    /// the stream is sorted by increasing time
+    pub increasing: bool,
+    /// links identical to a previous link with the same time, whatever the order of their nodes
+    pub duplicates: usize,
+    pub self_loops: usize,
+    /// greatest node id
+    pub max_id: Node,
+    /// ids between 0 and `max_id` without any link
+    pub isolated: usize,
+    /// minimum, median and maximum number of distinct neighbours of the nodes
+    pub degree_min: usize,
+    pub degree_median: f64,
+    pub degree_max: usize,
+}
+
+/// Summarize the stream in one pass.
+///
+/// Duplicates are only looked for among links sharing the same time, so they may be missed in unsorted streams.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::stats::summary;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 9},
+///     Link {node1: 1, node2: 0, time: 9},
+///     Link {node1: 1, node2: 3, time: 5},
+///     Link {node1: 3, node2: 3, time: 2},
+/// ];
+/// let summary = summary(&mut links.into_iter());
+/// assert_eq!((summary.nodes, summary.links, summary.pairs), (3, 4, 3));
+/// assert_eq!((summary.t_min, summary.t_max), (2, 9));
+/// assert!(summary.decreasing && !summary.increasing);
+/// assert_eq!((summary.duplicates, summary.self_loops), (1, 1));
+/// assert_eq!((summary.max_id, summary.isolated), (3, 1));
+/// assert_eq!((summary.degree_min, summary.degree_median, summary.degree_max), (1, 1., 2));
+/// ```
+pub fn summary(links: &mut LinkIterator) -> Summary {
+    let mut nodes: HashSet<Node> = HashSet::new();
+    let mut pairs: HashSet<(Node, Node)> = HashSet::new();
+    let mut current: HashSet<(Node, Node)> = HashSet::new();
+    let mut summary = Summary {
+        nodes: 0, links: 0, pairs: 0,
+        t_min: Time::MAX, t_max: 0,
+        decreasing: true, increasing: true,
+        duplicates: 0, self_loops: 0,
+        max_id: 0, isolated: 0,
+        degree_min: 0, degree_median: 0., degree_max: 0,
+    };
+    let mut previous: Option<Time> = None;
+    for link in links {
+        let (u, v, t) = (link.node1, link.node2, link.time);
+        let pair = if u <= v { (u, v) } else { (v, u) };
+        if let Some(previous) = previous {
+            if t > previous { summary.decreasing = false; }
+            if t < previous { summary.increasing = false; }
+            if t != previous { current.clear(); }
+        }
+        previous = Some(t);
+        if !current.insert(pair) { summary.duplicates += 1; }
+        if u == v { summary.self_loops += 1; }
+        summary.links += 1;
+        summary.t_min = summary.t_min.min(t);
+        summary.t_max = summary.t_max.max(t);
+        summary.max_id = summary.max_id.max(pair.1);
+        nodes.insert(u);
+        nodes.insert(v);
+        pairs.insert(pair);
+    }
+    if summary.links == 0 { summary.t_min = 0; }
+    summary.nodes = nodes.len();
+    summary.pairs = pairs.len();
+    summary.isolated = if nodes.is_empty() { 0 } else { summary.max_id + 1 - nodes.len() };
+    let mut degrees: HashMap<Node, usize> = nodes.iter().map(|&node| (node, 0)).collect();
+    for &(u, v) in pairs.iter().filter(|&&(u, v)| u != v) {
+        *degrees.get_mut(&u).unwrap() += 1;
+        *degrees.get_mut(&v).unwrap() += 1;
+    }
+    let mut degrees: Vec<usize> = degrees.into_values().collect();
+    degrees.sort();
+    if let (Some(&min), Some(&max)) = (degrees.first(), degrees.last()) {
+        let n = degrees.len();
+        summary.degree_min = min;
+        summary.degree_max = max;
+        summary.degree_median = (degrees[(n - 1) / 2] + degrees[n / 2]) as f64 / 2.;
+    }
+    summary
+}
+
+// ////////////////////////////
+//        DELTA-DEGREES
+// ////////////////////////////
+
+/// Decorate a LinkIterator, giving at each time `t` of the stream the delta-degree of the nodes:
+/// their number of distinct neighbours within `[t, t + delta]`.
+///
+/// The stream is expected by decreasing time. Only times such that `t + delta` does not exceed the
+/// greatest time are given, and only nodes with a positive delta-degree.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::stats::DeltaDegrees;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 10},
+///     Link {node1: 1, node2: 2, time: 8},
+///     Link {node1: 0, node2: 1, time: 5},
+///     Link {node1: 2, node2: 3, time: 1},
+/// ];
+/// let degrees: Vec<_> = DeltaDegrees::new(&mut links.into_iter(), 4).collect();
+/// assert_eq!(degrees, vec![
+///     (5, vec![(0, 1), (1, 2), (2, 1)]),
+///     (1, vec![(0, 1), (1, 1), (2, 1), (3, 1)]),
+/// ]);
+/// ```
+pub struct DeltaDegrees<'a> {
+    iter: &'a mut LinkIterator,
+    delta: Time,
+    t_max: Option<Time>,
+    window: VecDeque<Link>,
+    counts: HashMap<(Node, Node), usize>,
+    degrees: BTreeMap<Node, usize>,
+    pending: Option<Link>,
+}
+
+impl<'a> DeltaDegrees<'a> {
+    /// Decorate a LinkIterator for computing delta-degrees.
+    pub fn new(iterator: &'a mut LinkIterator, delta: Time) -> DeltaDegrees<'a> {
+        DeltaDegrees {
+            iter: iterator,
+            delta,
+            t_max: None,
+            window: VecDeque::new(),
+            counts: HashMap::new(),
+            degrees: BTreeMap::new(),
+            pending: None,
+        }
+    }
+
+    fn add(&mut self, link: Link) {
+        if link.node1 == link.node2 { return; }
+        let pair = if link.node1 < link.node2 { (link.node1, link.node2) } else { (link.node2, link.node1) };
+        let count = self.counts.entry(pair).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            *self.degrees.entry(pair.0).or_insert(0) += 1;
+            *self.degrees.entry(pair.1).or_insert(0) += 1;
+        }
+        self.window.push_back(link);
+    }
+
+    fn remove_after(&mut self, t: Time) {
+        while self.window.front().is_some_and(|link| link.time > t) {
+            let link = self.window.pop_front().unwrap();
+            let pair = if link.node1 < link.node2 { (link.node1, link.node2) } else { (link.node2, link.node1) };
+            let count = self.counts.get_mut(&pair).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&pair);
+                for node in [pair.0, pair.1] {
+                    let degree = self.degrees.get_mut(&node).unwrap();
+                    *degree -= 1;
+                    if *degree == 0 { self.degrees.remove(&node); }
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for DeltaDegrees<'a> {
+    type Item = (Time, Vec<(Node, usize)>);
+    fn next(&mut self) -> Option<(Time, Vec<(Node, usize)>)> {
+        loop {
+            let first = self.pending.take().or_else(|| self.iter.next())?;
+            let t = first.time;
+            let t_max = *self.t_max.get_or_insert(t);
+            self.remove_after(t.saturating_add(self.delta));
+            self.add(first);
+            while let Some(link) = self.iter.next() {
+                if link.time != t {
+                    self.pending = Some(link);
+                    break;
+                }
+                self.add(link);
+            }
+            if t.checked_add(self.delta).is_some_and(|end| end <= t_max) {
+                return Some((t, self.degrees.iter().map(|(&node, &degree)| (node, degree)).collect()));
+            }
+        }
+    }
+}
+
+/// Delta-density of the stream: the mean over its times `t` of the ratio of pairs among `size`
+/// nodes linked within `[t, t + delta]`, the times being those given by `DeltaDegrees`.
+/// It is 0 with less than 2 nodes.
+///
+/// This is a per-event average: each time of the stream counts once whatever the duration until
+/// the next one, so bursts of events weigh more than long quiet periods.
+///
+/// # Example
+/// ```
+/// # use linkstreams::algo::stats::delta_density;
+/// # use linkstreams::data::link::Link;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 10},
+///     Link {node1: 1, node2: 2, time: 8},
+///     Link {node1: 0, node2: 1, time: 5},
+///     Link {node1: 2, node2: 3, time: 1},
+/// ];
+/// assert_eq!(delta_density(&mut links.clone().into_iter(), 4, 4), (2. / 6. + 2. / 6.) / 2.);
+/// assert_eq!(delta_density(&mut links.into_iter(), 4, 1), 0.);
+/// ```
+pub fn delta_density(links: &mut LinkIterator, delta: Time, size: usize) -> f64 {
+    if size < 2 { return 0.; }
+    let pairs = (size * (size - 1) / 2) as f64;
+    let mut sum = 0.;
+    let mut count = 0;
+    for (_, degrees) in DeltaDegrees::new(links, delta) {
+        let linked: usize = degrees.iter().map(|&(_, degree)| degree).sum::<usize>() / 2;
+        sum += linked as f64 / pairs;
+        count += 1;
+    }
+    if count == 0 { 0. } else { sum / count as f64 }
+}
+
+// /////////////
+// / TEST ZONE /
+// /////////////
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "Delta must be positive")]
+    fn activity_zero_delta_test() {
+        let links = vec![Link {node1: 0, node2: 1, time: 3}, Link {node1: 1, node2: 2, time: 0}];
+        activity(&mut links.into_iter(), 2, Some(0));
+    }
+}
@@ -0,0 +1,184 @@
+use data::link::Node;
+use data::matrix::ReachGraph;
+
+const BITS: usize = 64;
+
+/// A fixed size set of nodes stored as bits.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::bitset::BitSet;
+/// let mut set = BitSet::from_bools(&vec![true, false, true, true]);
+/// set.intersect_with(&BitSet::from_bools(&vec![true, true, false, true]));
+/// assert_eq!(set.len(), 2);
+/// assert_eq!(set.to_set(), vec![0, 3]);
+/// assert_eq!(set.to_bools(), vec![true, false, false, true]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    blocks: Vec<u64>,
+    size: usize,
+}
+
+impl BitSet {
+    /// Create an empty set able to hold nodes from 0 to `size` - 1
+    pub fn new(size: usize) -> BitSet {
+        BitSet {
+            blocks: vec![0; size.div_ceil(BITS)],
+            size,
+        }
+    }
+
+    /// Create the set of the indices set to true in `bools`
+    pub fn from_bools(bools: &[bool]) -> BitSet {
+        let mut set = BitSet::new(bools.len());
+        for (node, &b) in bools.iter().enumerate() {
+            if b { set.insert(node); }
+        }
+        set
+    }
+
+    pub fn insert(&mut self, node: Node) {
+        assert!(node < self.size, "BitSet out of range writting!");
+        self.blocks[node / BITS] |= 1 << (node % BITS);
+    }
+
+    pub fn remove(&mut self, node: Node) {
+        assert!(node < self.size, "BitSet out of range writting!");
+        self.blocks[node / BITS] &= !(1 << (node % BITS));
+    }
+
+    pub fn contains(&self, node: Node) -> bool {
+        node < self.size && self.blocks[node / BITS] & (1 << (node % BITS)) != 0
+    }
+
+    /// Keep only the nodes also present in `other`
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        for (block, other_block) in self.blocks.iter_mut().zip(other.blocks.iter()) {
+            *block &= *other_block;
+        }
+    }
+
+    /// Number of nodes in the set
+    pub fn len(&self) -> usize {
+        self.blocks.iter().map(|block| block.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.iter().all(|&block| block == 0)
+    }
+
+    /// Nodes of the set in increasing order
+    pub fn to_set(&self) -> Vec<Node> {
+        self.iter().collect()
+    }
+
+    /// Iterate over the nodes of the set in increasing order, skipping empty blocks
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item=Node> + 'a {
+        self.blocks.iter().enumerate().flat_map(|(i, &block)| {
+            let mut block = block;
+            ::std::iter::from_fn(move || {
+                if block == 0 { return None; }
+                let bit = block.trailing_zeros() as usize;
+                block &= block - 1;
+                Some(i * BITS + bit)
+            })
+        })
+    }
+
+    /// Boolean vector representation of the set, as used by existence traces
+    pub fn to_bools(&self) -> Vec<bool> {
+        (0..self.size).map(|node| self.contains(node)).collect()
+    }
+}
+
+/// A square boolean matrix stored as one bitset per row, for relation graphs between nodes.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::bitset::{BitMatrix, BitSet};
+/// # use linkstreams::data::matrix::ReachGraph;
+/// let mut m = BitMatrix::new(3, true);
+/// m.remove(0, 1);
+/// m.intersect_row(2, &BitSet::from_bools(&[true, false, true]));
+/// assert_eq!(m.successors(0), vec![0, 2]);
+/// assert_eq!(m.predecessors(1), vec![1]);
+/// assert!(m.is_subset_clique(&|node| node != 1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    rows: Vec<BitSet>,
+}
+
+impl BitMatrix {
+    /// Create a `size`x`size` matrix filled with `value`
+    pub fn new(size: usize, value: bool) -> BitMatrix {
+        let mut row = BitSet::new(size);
+        if value {
+            for node in 0..size { row.insert(node); }
+        }
+        BitMatrix { rows: vec![row; size] }
+    }
+
+    pub fn contains(&self, x: Node, y: Node) -> bool {
+        self.rows[x].contains(y)
+    }
+
+    pub fn insert(&mut self, x: Node, y: Node) {
+        self.rows[x].insert(y);
+    }
+
+    pub fn remove(&mut self, x: Node, y: Node) {
+        self.rows[x].remove(y);
+    }
+
+    /// Nodes in relation with `x`
+    pub fn row(&self, x: Node) -> &BitSet {
+        &self.rows[x]
+    }
+
+    /// Keep in relation with `x` only the nodes of `set`
+    pub fn intersect_row(&mut self, x: Node, set: &BitSet) {
+        self.rows[x].intersect_with(set);
+    }
+}
+
+impl ReachGraph for BitMatrix {
+    fn size(&self) -> usize { self.rows.len() }
+
+    fn reaches(&self, x: Node, y: Node) -> bool { self.contains(x, y) }
+
+    fn successors(&self, node: Node) -> Vec<Node> { self.rows[node].to_set() }
+}
+
+// /////////////
+// / TEST ZONE /
+// /////////////
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    fn bitset_insert_remove_test() {
+        let mut set = BitSet::new(130);
+        assert!(set.is_empty());
+        set.insert(0);
+        set.insert(64);
+        set.insert(129);
+        assert_eq!(vec![0, 64, 129], set.to_set());
+        set.remove(64);
+        assert!(!set.contains(64));
+        assert_eq!(2, set.len());
+    }
+
+    #[test]
+    fn bitset_intersect_test() {
+        let mut small = BitSet::from_bools(&[false, true, true]);
+        let big = BitSet::from_bools(&[true, true, true]);
+        small.intersect_with(&big);
+        assert_eq!(BitSet::from_bools(&[false, true, true]), small);
+        let mut big = big;
+        big.intersect_with(&BitSet::new(3));
+        assert!(big.is_empty());
+    }
+}
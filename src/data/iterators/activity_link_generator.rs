@@ -0,0 +1,67 @@
+use data::link::{Link, Node, Time};
+use data::iterators::link_generator::*;
+use data::rand::rngs::StdRng;
+use data::rand::Rng;
+use data::rand::seq::index;
+
+/// Generate an activity-driven linkstream, as in Perra et al., *Activity driven modeling of time varying networks*.
+///
+/// Each node has an activity `a` drawn from a power law of exponent `gamma` on `[epsilon, 1]`,
+/// and is active at each time with probability `a`. An active node links to `m` other nodes
+/// chosen uniformly. Activation times are sampled directly, and the stream is generated at creation.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::iterators::activity_link_generator::ActivityLinkGenerator;
+/// # use linkstreams::data::iterators::link_generator::rng_from_seed;
+/// let generator = ActivityLinkGenerator::new(50, 1000, 2.1, 0.01, 2, rng_from_seed(Some(0)));
+/// assert_eq!(generator.activities().len(), 50);
+/// assert!(generator.activities().iter().all(|&a| 0.01 <= a && a <= 1.));
+/// let links: Vec<_> = generator.collect();
+/// assert!(links.windows(2).all(|w| w[0].time >= w[1].time));
+/// ```
+pub struct ActivityLinkGenerator {
+    nb_nodes: usize,
+    time_max: Time,
+    activities: Vec<f64>,
+    links: ::std::vec::IntoIter<Link>,
+}
+
+impl ActivityLinkGenerator {
+    pub fn new(nb_nodes: usize, time_max: Time, gamma: f64, epsilon: f64, m: usize, mut rng: StdRng) -> ActivityLinkGenerator {
+        assert!(0. < epsilon && epsilon <= 1., "The minimal activity must be in (0, 1]");
+        assert!(gamma != 1., "The activity exponent must not be 1");
+        assert!(m < nb_nodes, "Active nodes can not link to more than all the other nodes");
+        let low = epsilon.powf(1. - gamma);
+        let activities: Vec<f64> = (0..nb_nodes)
+            .map(|_| (low + rng.gen::<f64>() * (1. - low)).powf(1. / (1. - gamma)).max(epsilon).min(1.))
+            .collect();
+        let mut links: Vec<Link> = Vec::new();
+        for (node, &activity) in activities.iter().enumerate() {
+            for time in bernoulli_times(activity, time_max, &mut rng) {
+                for other in index::sample(&mut rng, nb_nodes - 1, m) {
+                    let other: Node = if other < node { other } else { other + 1 };
+                    links.push(Link { node1: node.min(other), node2: node.max(other), time });
+                }
+            }
+        }
+        ActivityLinkGenerator { nb_nodes, time_max, activities, links: sorted_links(links) }
+    }
+
+    /// Activity of each node.
+    pub fn activities(&self) -> &[f64] {
+        &self.activities
+    }
+}
+
+impl LinkGenerator for ActivityLinkGenerator {
+    fn nb_nodes(&self) -> usize { self.nb_nodes }
+    fn time_max(&self) -> Time { self.time_max }
+}
+
+impl Iterator for ActivityLinkGenerator {
+    type Item = Link;
+    fn next(&mut self) -> Option<Link> {
+        self.links.next()
+    }
+}
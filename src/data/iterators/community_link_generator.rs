@@ -0,0 +1,74 @@
+use data::link::{Link, Node, Time};
+use data::iterators::link_generator::*;
+use data::rand::rngs::StdRng;
+use data::rand::Rng;
+
+/// Generate a linkstream with planted communities, known to be delta-connected.
+///
+/// Nodes are split into `groups` communities of consecutive nodes. Each pair of nodes of a community
+/// is linked at least once in every interval `[t, t + delta]`, with gaps drawn uniformly between 1
+/// and `delta`, so that each community is a delta-clique. Other pairs are linked at each time with
+/// probability `noise`. Without noise, the delta-components of the stream are its communities.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::iterators::community_link_generator::CommunityLinkGenerator;
+/// # use linkstreams::data::iterators::link_generator::rng_from_seed;
+/// let generator = CommunityLinkGenerator::new(5, 100, 10, 2, 0., rng_from_seed(Some(0)));
+/// assert_eq!(generator.communities(), vec![vec![0, 1, 2], vec![3, 4]]);
+/// let links: Vec<_> = generator.collect();
+/// assert!(links.iter().all(|link| (link.node1 < 3) == (link.node2 < 3)));
+/// ```
+pub struct CommunityLinkGenerator {
+    nb_nodes: usize,
+    time_max: Time,
+    groups: usize,
+    links: ::std::vec::IntoIter<Link>,
+}
+
+impl CommunityLinkGenerator {
+    pub fn new(nb_nodes: usize, time_max: Time, delta: Time, groups: usize, noise: f64, mut rng: StdRng) -> CommunityLinkGenerator {
+        assert!(delta > 0, "Delta must be positive");
+        assert!(0 < groups && groups <= nb_nodes, "There must be between 1 and nbNodes communities");
+        let community = |node: Node| node * groups / nb_nodes;
+        let mut links: Vec<Link> = Vec::new();
+        for node1 in 0..nb_nodes {
+            for node2 in node1 + 1..nb_nodes {
+                let times: Vec<Time> = if community(node1) == community(node2) {
+                    let mut times: Vec<Time> = Vec::new();
+                    let mut time = rng.gen_range(0..=delta);
+                    while time <= time_max {
+                        times.push(time);
+                        time += rng.gen_range(1..=delta);
+                    }
+                    times
+                } else {
+                    bernoulli_times(noise, time_max, &mut rng)
+                };
+                links.extend(times.into_iter().map(|time| Link { node1, node2, time }));
+            }
+        }
+        CommunityLinkGenerator { nb_nodes, time_max, groups, links: sorted_links(links) }
+    }
+
+    /// Planted communities, the ground truth of the delta-components.
+    pub fn communities(&self) -> Vec<Vec<Node>> {
+        let mut communities: Vec<Vec<Node>> = vec![Vec::new(); self.groups];
+        for node in 0..self.nb_nodes {
+            communities[node * self.groups / self.nb_nodes].push(node);
+        }
+        communities
+    }
+}
+
+impl LinkGenerator for CommunityLinkGenerator {
+    fn nb_nodes(&self) -> usize { self.nb_nodes }
+    fn time_max(&self) -> Time { self.time_max }
+}
+
+impl Iterator for CommunityLinkGenerator {
+    type Item = Link;
+    fn next(&mut self) -> Option<Link> {
+        self.links.next()
+    }
+}
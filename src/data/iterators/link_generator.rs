@@ -0,0 +1,172 @@
+use data::link::{Link, Time};
+use data::rand::{Rng, SeedableRng};
+use data::rand::rngs::StdRng;
+
+/// Random linkstream generators.
+///
+/// Generators are iterators of links by decreasing time, between `0` and `time_max()` and among
+/// the nodes `0..nb_nodes()`. They draw from a `StdRng`, so that a seed gives a reproducible stream.
+pub trait LinkGenerator: Iterator<Item=Link> {
+    /// Number of nodes of the generated stream.
+    fn nb_nodes(&self) -> usize;
+    /// Greatest time of the generated stream.
+    fn time_max(&self) -> Time;
+}
+
+/// Random number generator seeded with `seed`, or from the system entropy if there is none.
+///
+/// # Example
+/// ```
+/// # extern crate rand;
+/// # extern crate linkstreams;
+/// # use linkstreams::data::iterators::link_generator::rng_from_seed;
+/// use rand::Rng;
+/// let x: u64 = rng_from_seed(Some(42)).gen();
+/// let y: u64 = rng_from_seed(Some(42)).gen();
+/// assert_eq!(x, y);
+/// ```
+pub fn rng_from_seed(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Times of a Bernoulli process of parameter `proba` on `0..=time_max`, by increasing order.
+///
+/// Each time is drawn with probability `proba`, sampling directly the geometric gaps between
+/// drawn times rather than testing each time.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::iterators::link_generator::{bernoulli_times, rng_from_seed};
+/// let mut rng = rng_from_seed(Some(0));
+/// assert_eq!(bernoulli_times(1., 3, &mut rng), vec![0, 1, 2, 3]);
+/// assert!(bernoulli_times(0., 3, &mut rng).is_empty());
+/// assert!(bernoulli_times(0.1, 1000, &mut rng).windows(2).all(|w| w[0] < w[1]));
+/// ```
+pub fn bernoulli_times<R: Rng>(proba: f64, time_max: Time, rng: &mut R) -> Vec<Time> {
+    assert!((0. ..=1.).contains(&proba), "The probability must be between 0 and 1");
+    let mut times: Vec<Time> = Vec::new();
+    if proba == 0. { return times; }
+    let mut time: f64 = 0.;
+    loop {
+        if proba < 1. {
+            time += ((1. - rng.gen::<f64>()).ln() / (1. - proba).ln()).floor();
+        }
+        if time > time_max as f64 { break; }
+        times.push(time as Time);
+        time += 1.;
+    }
+    times
+}
+
+/// Exponentially distributed duration of rate `rate`.
+pub fn exponential<R: Rng>(rate: f64, rng: &mut R) -> f64 {
+    -(1. - rng.gen::<f64>()).ln() / rate
+}
+
+/// Sort generated links by decreasing time then increasing nodes, removing duplicates.
+pub fn sorted_links(mut links: Vec<Link>) -> ::std::vec::IntoIter<Link> {
+    links.sort_by(|l1, l2| l2.time.cmp(&l1.time).then((l1.node1, l1.node2).cmp(&(l2.node1, l2.node2))));
+    links.dedup();
+    links.into_iter()
+}
+
+// /////////////
+// / TEST ZONE /
+// /////////////
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use algo;
+    use data::link::Node;
+    use algo::stats::burstiness;
+    use data::iterators::uniform_link_generator::UnifLinkGenerator;
+    use data::iterators::activity_link_generator::ActivityLinkGenerator;
+    use data::iterators::poisson_link_generator::{PoissonLinkGenerator, PointProcess};
+    use data::iterators::community_link_generator::CommunityLinkGenerator;
+    use data::iterators::planted_link_generator::PlantedLinkGenerator;
+
+    /// Check that a generator gives a valid linkstream: times by decreasing order and at most
+    /// `time_max()`, nodes below `nb_nodes()` and no self loop. Return the generated links.
+    fn check_generator(generator: &mut dyn LinkGenerator) -> Vec<Link> {
+        let (nb_nodes, time_max): (Node, Time) = (generator.nb_nodes(), generator.time_max());
+        let links: Vec<Link> = generator.collect();
+        for window in links.windows(2) {
+            assert!(window[0].time >= window[1].time, "Generated links are not sorted by decreasing time");
+        }
+        for link in &links {
+            assert!(link.time <= time_max, "Generated link after the greatest time");
+            assert!(link.node1 < nb_nodes && link.node2 < nb_nodes, "Generated link with an unknown node");
+            assert!(link.node1 != link.node2, "Generated self loop");
+        }
+        links
+    }
+
+    #[test]
+    fn uniform_generator_test() {
+        let links = check_generator(&mut UnifLinkGenerator::new(10, 100, 0.1, rng_from_seed(Some(1))));
+        assert!(!links.is_empty());
+        let again = check_generator(&mut UnifLinkGenerator::new(10, 100, 0.1, rng_from_seed(Some(1))));
+        assert_eq!(links, again);
+        assert_eq!(check_generator(&mut UnifLinkGenerator::new(10, 100, 0., rng_from_seed(None))).len(), 0);
+        assert_eq!(check_generator(&mut UnifLinkGenerator::new(10, 100, 1., rng_from_seed(None))).len(), 45 * 101);
+    }
+
+    #[test]
+    fn activity_generator_test() {
+        let links = check_generator(&mut ActivityLinkGenerator::new(20, 500, 2.1, 0.05, 2, rng_from_seed(Some(1))));
+        assert!(!links.is_empty());
+        assert_eq!(links, check_generator(&mut ActivityLinkGenerator::new(20, 500, 2.1, 0.05, 2, rng_from_seed(Some(1)))));
+    }
+
+    #[test]
+    fn poisson_generator_test() {
+        let poisson = PointProcess::Poisson { rate: 0.02 };
+        let links = check_generator(&mut PoissonLinkGenerator::new(10, 5000, poisson, rng_from_seed(Some(1))));
+        assert_eq!(links, check_generator(&mut PoissonLinkGenerator::new(10, 5000, poisson, rng_from_seed(Some(1)))));
+        // Hawkes inter-event times are burstier than Poisson ones
+        let hawkes = PointProcess::Hawkes { rate: 0.005, alpha: 0.8, decay: 1. };
+        let mut rng = rng_from_seed(Some(1));
+        let gaps = |times: Vec<Time>| -> Vec<Time> { times.windows(2).map(|w| w[1] - w[0]).collect() };
+        let poisson_b = burstiness(&gaps(poisson.sample(100000, &mut rng)));
+        let hawkes_b = burstiness(&gaps(hawkes.sample(100000, &mut rng)));
+        assert!(poisson_b.abs() < 0.1);
+        assert!(hawkes_b > poisson_b + 0.2);
+        check_generator(&mut PoissonLinkGenerator::new(10, 5000, hawkes, rng_from_seed(Some(1))));
+    }
+
+    #[test]
+    fn community_generator_test() {
+        let (nb_nodes, delta) = (9, 20);
+        let generator = CommunityLinkGenerator::new(nb_nodes, 1000, delta, 3, 0., rng_from_seed(Some(1)));
+        let truth = generator.communities();
+        let links = check_generator(&mut CommunityLinkGenerator::new(nb_nodes, 1000, delta, 3, 0., rng_from_seed(Some(1))));
+        let nodes: Vec<Node> = (0..nb_nodes).collect();
+        let (mut lower, _) = algo::delta_components_lower(&mut links.clone().into_iter(), nb_nodes, delta, &nodes, &|_| true);
+        let (mut upper, _) = algo::delta_components_upper(&mut links.into_iter(), nb_nodes, delta, &nodes, &|_| true);
+        for comps in [&mut lower, &mut upper] {
+            for comp in comps.iter_mut() { comp.sort(); }
+            comps.sort();
+        }
+        assert_eq!(lower, truth);
+        assert_eq!(upper, truth);
+    }
+
+    #[test]
+    fn planted_generator_test() {
+        let (nb_nodes, delta) = (20, 20);
+        let mut generator = PlantedLinkGenerator::new(nb_nodes, 2000, delta, 3, 0., rng_from_seed(Some(1)));
+        let truth = generator.planted().to_vec();
+        let links = check_generator(&mut generator);
+        let nodes: Vec<Node> = (0..nb_nodes).collect();
+        let (comps, _) = algo::delta_components_lower(&mut links.clone().into_iter(), nb_nodes, delta, &nodes, &|_| true);
+        let found: Vec<(Time, Time, Vec<Node>)> = comps.into_iter().map(|comp| (0, 2000, comp)).collect();
+        assert_eq!(algo::benchmark::score(&found, &algo::benchmark::components_truth(&truth), true), (1., 1., 1.));
+        let (start, stop, vec) = algo::largest_boxe(&mut links.into_iter(), &nodes, delta);
+        let (precision, recall, _) = algo::benchmark::score(&[(start, stop + delta, vec)], &algo::benchmark::rectangle_truth(&truth), false);
+        assert_eq!(precision, 1.);
+        assert!(recall > 0.99);
+    }
+}
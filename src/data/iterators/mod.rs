@@ -1,4 +1,11 @@
 pub mod link_iterator;
 pub mod stdin_link_iterator;
 pub mod rename_link_iterator;
+pub mod link_generator;
 pub mod uniform_link_generator;
+pub mod snapshot_iterator;
+pub mod shuffle_link_iterator;
+pub mod activity_link_generator;
+pub mod poisson_link_generator;
+pub mod community_link_generator;
+pub mod planted_link_generator;
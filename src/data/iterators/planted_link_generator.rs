@@ -0,0 +1,94 @@
+use data::link::{Link, Node, Time};
+use data::iterators::link_generator::*;
+use data::rand::rngs::StdRng;
+use data::rand::Rng;
+use data::rand::seq::SliceRandom;
+
+/// Generate a benchmark linkstream with planted delta-cliques in background noise.
+///
+/// `groups` disjoint sets of `nb_nodes / (groups + 1)` random nodes are planted as delta-cliques:
+/// each of their pairs is linked at least once in every interval of duration `delta` of their
+/// time interval. The first half of the groups span the whole stream, so that they are
+/// delta-components, and the others a random interval of between a quarter and a half of it.
+/// Every pair of nodes is also linked at each time with probability `noise`.
+///
+/// The planted cliques are the ground truth of `calc comps`, `calc exist lr` and `calc part`,
+/// see `algo::benchmark`. The stream is generated at creation.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::iterators::planted_link_generator::PlantedLinkGenerator;
+/// # use linkstreams::data::iterators::link_generator::rng_from_seed;
+/// let generator = PlantedLinkGenerator::new(12, 1000, 10, 3, 0., rng_from_seed(Some(0)));
+/// let planted = generator.planted().to_vec();
+/// assert_eq!(planted.len(), 3);
+/// assert!(planted.iter().all(|&(_, _, ref nodes)| nodes.len() == 3));
+/// assert_eq!((planted[0].0, planted[0].1), (0, 1000));
+/// assert_eq!((planted[1].0, planted[1].1), (0, 1000));
+/// let links: Vec<_> = generator.collect();
+/// assert!(links.iter().all(|link| planted.iter().any(|&(start, stop, ref nodes)| {
+///     nodes.contains(&link.node1) && nodes.contains(&link.node2) && start <= link.time && link.time <= stop
+/// })));
+/// ```
+pub struct PlantedLinkGenerator {
+    nb_nodes: usize,
+    time_max: Time,
+    planted: Vec<(Time, Time, Vec<Node>)>,
+    links: ::std::vec::IntoIter<Link>,
+}
+
+impl PlantedLinkGenerator {
+    pub fn new(nb_nodes: usize, time_max: Time, delta: Time, groups: usize, noise: f64, mut rng: StdRng) -> PlantedLinkGenerator {
+        assert!(delta > 0, "Delta must be positive");
+        let size = nb_nodes / (groups + 1);
+        assert!(size >= 2, "Not enough nodes for planting the groups");
+        let mut nodes: Vec<Node> = (0..nb_nodes).collect();
+        nodes.shuffle(&mut rng);
+        let mut planted: Vec<(Time, Time, Vec<Node>)> = Vec::with_capacity(groups);
+        for (i, group) in nodes.chunks(size).take(groups).enumerate() {
+            let (start, stop) = if i < groups.div_ceil(2) { (0, time_max) } else {
+                let duration = rng.gen_range(time_max / 4..=time_max / 2);
+                let start = rng.gen_range(0..=time_max - duration);
+                (start, start + duration)
+            };
+            let mut group = group.to_vec();
+            group.sort();
+            planted.push((start, stop, group));
+        }
+        let mut links: Vec<Link> = Vec::new();
+        for &(start, stop, ref group) in &planted {
+            for (i, &node1) in group.iter().enumerate() {
+                for &node2 in &group[i + 1..] {
+                    let mut time = start + rng.gen_range(0..=delta);
+                    while time <= stop {
+                        links.push(Link { node1, node2, time });
+                        time += rng.gen_range(1..=delta);
+                    }
+                }
+            }
+        }
+        for node1 in 0..nb_nodes {
+            for node2 in node1 + 1..nb_nodes {
+                links.extend(bernoulli_times(noise, time_max, &mut rng).into_iter().map(|time| Link { node1, node2, time }));
+            }
+        }
+        PlantedLinkGenerator { nb_nodes, time_max, planted, links: sorted_links(links) }
+    }
+
+    /// Planted delta-cliques `(start, stop, nodes)`.
+    pub fn planted(&self) -> &[(Time, Time, Vec<Node>)] {
+        &self.planted
+    }
+}
+
+impl LinkGenerator for PlantedLinkGenerator {
+    fn nb_nodes(&self) -> usize { self.nb_nodes }
+    fn time_max(&self) -> Time { self.time_max }
+}
+
+impl Iterator for PlantedLinkGenerator {
+    type Item = Link;
+    fn next(&mut self) -> Option<Link> {
+        self.links.next()
+    }
+}
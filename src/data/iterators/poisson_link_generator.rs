@@ -0,0 +1,108 @@
+use data::link::{Link, Time};
+use data::iterators::link_generator::*;
+use data::rand::rngs::StdRng;
+use data::rand::Rng;
+
+/// Point processes giving the times of the links of a pair of nodes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointProcess {
+    /// Homogeneous Poisson process of intensity `rate`, with exponential inter-event times.
+    Poisson { rate: f64 },
+    /// Self-exciting Hawkes process of intensity `rate + alpha * sum(exp(-decay * (t - t_i)))` over
+    /// the previous events `t_i`, giving bursts and heavy-tailed inter-event times. It is
+    /// stationary when `alpha < decay`.
+    Hawkes { rate: f64, alpha: f64, decay: f64 },
+}
+
+impl PointProcess {
+    /// Sample the events of the process on `[0, time_max + 1)`, rounded down to times.
+    ///
+    /// The process is sampled in continuous time, with Ogata's thinning method for Hawkes
+    /// processes. Several events at the same time give a single one.
+    ///
+    /// # Example
+    /// ```
+    /// # use linkstreams::data::iterators::poisson_link_generator::PointProcess;
+    /// # use linkstreams::data::iterators::link_generator::rng_from_seed;
+    /// let mut rng = rng_from_seed(Some(0));
+    /// let times = PointProcess::Poisson { rate: 0.1 }.sample(10000, &mut rng);
+    /// assert!(800 < times.len() && times.len() < 1200);
+    /// let times = PointProcess::Hawkes { rate: 0.01, alpha: 0.5, decay: 1. }.sample(10000, &mut rng);
+    /// assert!(times.windows(2).all(|w| w[0] < w[1]));
+    /// ```
+    pub fn sample<R: Rng>(&self, time_max: Time, rng: &mut R) -> Vec<Time> {
+        let end = (time_max + 1) as f64;
+        let mut times: Vec<Time> = Vec::new();
+        let mut t: f64 = 0.;
+        match *self {
+            PointProcess::Poisson { rate } => {
+                assert!(rate > 0., "The rate must be positive");
+                loop {
+                    t += exponential(rate, rng);
+                    if t >= end { break; }
+                    times.push(t as Time);
+                }
+            },
+            PointProcess::Hawkes { rate, alpha, decay } => {
+                assert!(rate > 0. && alpha >= 0. && decay > 0., "Invalid Hawkes process parameters");
+                // excitation of the previous events at time t
+                let mut excitation: f64 = 0.;
+                loop {
+                    let bound = rate + excitation;
+                    let w = exponential(bound, rng);
+                    t += w;
+                    if t >= end { break; }
+                    excitation *= (-decay * w).exp();
+                    if rng.gen::<f64>() * bound <= rate + excitation {
+                        excitation += alpha;
+                        times.push(t as Time);
+                    }
+                }
+            },
+        }
+        times.dedup();
+        times
+    }
+}
+
+/// Generate a linkstream where the links of each pair of nodes follow a point process.
+///
+/// The events of each pair are sampled directly, in `O(n² + links)`, and the stream is generated at creation.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::iterators::poisson_link_generator::{PoissonLinkGenerator, PointProcess};
+/// # use linkstreams::data::iterators::link_generator::rng_from_seed;
+/// let process = PointProcess::Poisson { rate: 0.01 };
+/// let links: Vec<_> = PoissonLinkGenerator::new(10, 1000, process, rng_from_seed(Some(0))).collect();
+/// assert!(links.iter().all(|link| link.node1 < link.node2 && link.time <= 1000));
+/// ```
+pub struct PoissonLinkGenerator {
+    nb_nodes: usize,
+    time_max: Time,
+    links: ::std::vec::IntoIter<Link>,
+}
+
+impl PoissonLinkGenerator {
+    pub fn new(nb_nodes: usize, time_max: Time, process: PointProcess, mut rng: StdRng) -> PoissonLinkGenerator {
+        let mut links: Vec<Link> = Vec::new();
+        for node1 in 0..nb_nodes {
+            for node2 in node1 + 1..nb_nodes {
+                links.extend(process.sample(time_max, &mut rng).into_iter().map(|time| Link { node1, node2, time }));
+            }
+        }
+        PoissonLinkGenerator { nb_nodes, time_max, links: sorted_links(links) }
+    }
+}
+
+impl LinkGenerator for PoissonLinkGenerator {
+    fn nb_nodes(&self) -> usize { self.nb_nodes }
+    fn time_max(&self) -> Time { self.time_max }
+}
+
+impl Iterator for PoissonLinkGenerator {
+    type Item = Link;
+    fn next(&mut self) -> Option<Link> {
+        self.links.next()
+    }
+}
@@ -0,0 +1,138 @@
+use data::link::{Link, Node, Time};
+use data::iterators::link_iterator::*;
+use data::rand::Rng;
+use data::rand::seq::SliceRandom;
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+
+/// Null models randomizing a linkstream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NullModel {
+    /// Permute the times of the links, keeping their pairs of nodes.
+    Timestamps,
+    /// Give the sequence of times of each pair to another pair, keeping the aggregated graph.
+    Sequences,
+    /// Shift the times of each pair by a random offset, cyclically over the time span of the stream.
+    Shifts,
+    /// Swap the ends of pairs of edges of the aggregated graph, keeping the degrees and the times of each edge.
+    Swaps,
+}
+
+#[derive(Debug)]
+pub struct NullModelParseError;
+impl FromStr for NullModel {
+    type Err = NullModelParseError;
+    /// Convert a model name into a NullModel
+    ///
+    /// # Example
+    /// ```
+    /// # use linkstreams::data::iterators::shuffle_link_iterator::NullModel;
+    /// # use std::str::FromStr;
+    /// assert_eq!(NullModel::from_str("shifts").unwrap(), NullModel::Shifts);
+    /// assert!(NullModel::from_str("shift").is_err());
+    /// ```
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "timestamps" => Ok(NullModel::Timestamps),
+            "sequences" => Ok(NullModel::Sequences),
+            "shifts" => Ok(NullModel::Shifts),
+            "swaps" => Ok(NullModel::Swaps),
+            _ => Err(NullModelParseError),
+        }
+    }
+}
+
+/// Decorate a LinkIterator, randomizing it with a null model.
+///
+/// The whole stream is read and randomized at creation, and links are given by decreasing time,
+/// then by increasing nodes. Pairs of nodes are undirected, the smallest node being given first.
+///
+/// # Example
+/// ```
+/// # extern crate rand;
+/// # extern crate linkstreams;
+/// # use linkstreams::data::link::Link;
+/// # use linkstreams::data::iterators::shuffle_link_iterator::{NullModel, ShuffleLinkIter};
+/// use rand::SeedableRng;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 9},
+///     Link {node1: 1, node2: 2, time: 5},
+///     Link {node1: 1, node2: 0, time: 2},
+/// ];
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+/// let shuffled: Vec<Link> = ShuffleLinkIter::new(&mut links.into_iter(), NullModel::Timestamps, &mut rng).collect();
+/// let times: Vec<_> = shuffled.iter().map(|link| link.time).collect();
+/// assert_eq!(times, vec![9, 5, 2]);
+/// let mut pairs: Vec<_> = shuffled.iter().map(|link| (link.node1, link.node2)).collect();
+/// pairs.sort();
+/// assert_eq!(pairs, vec![(0, 1), (0, 1), (1, 2)]);
+/// ```
+pub struct ShuffleLinkIter {
+    links: ::std::vec::IntoIter<Link>,
+}
+
+impl ShuffleLinkIter {
+    /// Decorate a LinkIterator for randomizing it with `model`, drawing with `rng`.
+    pub fn new<R: Rng>(iterator: &mut LinkIterator, model: NullModel, rng: &mut R) -> ShuffleLinkIter {
+        let mut timelines: BTreeMap<(Node, Node), Vec<Time>> = BTreeMap::new();
+        for link in iterator {
+            let pair = if link.node1 <= link.node2 { (link.node1, link.node2) } else { (link.node2, link.node1) };
+            timelines.entry(pair).or_default().push(link.time);
+        }
+        let (mut pairs, mut times): (Vec<(Node, Node)>, Vec<Vec<Time>>) = timelines.into_iter().unzip();
+        match model {
+            NullModel::Timestamps => {
+                let mut all: Vec<Time> = times.iter().flatten().cloned().collect();
+                all.shuffle(rng);
+                let mut all = all.into_iter();
+                for timeline in times.iter_mut() {
+                    for time in timeline.iter_mut() { *time = all.next().unwrap(); }
+                }
+            },
+            NullModel::Sequences => times.shuffle(rng),
+            NullModel::Shifts => {
+                let t_min = times.iter().flatten().cloned().min().unwrap_or(0);
+                let t_max = times.iter().flatten().cloned().max().unwrap_or(0);
+                let span = t_max - t_min + 1;
+                for timeline in times.iter_mut() {
+                    let offset = rng.gen_range(0..span);
+                    for time in timeline.iter_mut() { *time = t_min + (*time - t_min + offset) % span; }
+                }
+            },
+            NullModel::Swaps => swap_edges(&mut pairs, rng),
+        }
+        let mut links: Vec<Link> = Vec::new();
+        for (&(node1, node2), timeline) in pairs.iter().zip(times.iter()) {
+            links.extend(timeline.iter().map(|&time| Link { node1, node2, time }));
+        }
+        links.sort_by(|l1, l2| l2.time.cmp(&l1.time).then((l1.node1, l1.node2).cmp(&(l2.node1, l2.node2))));
+        ShuffleLinkIter { links: links.into_iter() }
+    }
+}
+
+// Try ten swaps per edge, rejecting the ones creating a self loop or an existing edge.
+fn swap_edges<R: Rng>(pairs: &mut [(Node, Node)], rng: &mut R) {
+    if pairs.len() < 2 { return; }
+    let mut existing: HashSet<(Node, Node)> = pairs.iter().cloned().collect();
+    for _ in 0..10 * pairs.len() {
+        let (i, j) = (rng.gen_range(0..pairs.len()), rng.gen_range(0..pairs.len()));
+        if i == j { continue; }
+        let (a, b) = pairs[i];
+        let (c, d) = if rng.gen_bool(0.5) { pairs[j] } else { (pairs[j].1, pairs[j].0) };
+        let (e1, e2) = ((a.min(d), a.max(d)), (c.min(b), c.max(b)));
+        if a == d || c == b || e1 == e2 || existing.contains(&e1) || existing.contains(&e2) { continue; }
+        existing.remove(&pairs[i]);
+        existing.remove(&pairs[j]);
+        existing.insert(e1);
+        existing.insert(e2);
+        pairs[i] = e1;
+        pairs[j] = e2;
+    }
+}
+
+impl Iterator for ShuffleLinkIter {
+    type Item = Link;
+    fn next(&mut self) -> Option<Link> {
+        self.links.next()
+    }
+}
@@ -0,0 +1,158 @@
+use data::link::{Link, Node, Time};
+use data::iterators::link_iterator::*;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// Aggregated weighted graph of the links occurring in `[start, stop)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub start: Time,
+    pub stop: Time,
+    /// number of links of each pair of nodes, the smallest node first
+    pub edges: BTreeMap<(Node, Node), usize>,
+}
+
+impl Snapshot {
+    /// Nodes having at least one link in the snapshot
+    pub fn nodes(&self) -> Vec<Node> {
+        let nodes: BTreeSet<Node> = self.edges.keys().flat_map(|&(u, v)| vec![u, v]).collect();
+        nodes.into_iter().collect()
+    }
+
+    /// Number of links aggregated in the snapshot
+    pub fn nb_links(&self) -> usize {
+        self.edges.values().sum()
+    }
+
+    /// Number of linked pairs of distinct nodes, self-loops being ignored
+    pub fn nb_edges(&self) -> usize {
+        self.edges.keys().filter(|&&(u, v)| u != v).count()
+    }
+
+    /// Ratio of pairs of active nodes which are linked
+    pub fn density(&self) -> f64 {
+        let n = self.nodes().len();
+        if n < 2 { return 0.; }
+        2. * self.nb_edges() as f64 / (n * (n - 1)) as f64
+    }
+
+    /// Connected components of the aggregated graph, by increasing smallest node
+    pub fn components(&self) -> Vec<Vec<Node>> {
+        let mut neighbours: BTreeMap<Node, Vec<Node>> = BTreeMap::new();
+        for &(u, v) in self.edges.keys() {
+            neighbours.entry(u).or_default().push(v);
+            neighbours.entry(v).or_default().push(u);
+        }
+        let mut marks: BTreeSet<Node> = BTreeSet::new();
+        let mut components: Vec<Vec<Node>> = Vec::new();
+        for &node in neighbours.keys() {
+            if marks.contains(&node) { continue; }
+            let mut component: Vec<Node> = Vec::new();
+            let mut stack: Vec<Node> = vec![node];
+            while let Some(u) = stack.pop() {
+                if marks.insert(u) {
+                    component.push(u);
+                    stack.extend(neighbours[&u].iter().cloned());
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components
+    }
+}
+
+/// Decorate a LinkIterator, aggregating links into snapshots of a time window.
+///
+/// The stream is expected by decreasing time. The first window ends just after the first link,
+/// and each following window is shifted `step` earlier, so windows overlap when `step < window`.
+/// Windows without any link give empty snapshots.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::link::Link;
+/// # use linkstreams::data::iterators::snapshot_iterator::SnapshotIter;
+/// let links = vec![
+///     Link {node1: 0, node2: 1, time: 9},
+///     Link {node1: 1, node2: 0, time: 8},
+///     Link {node1: 1, node2: 2, time: 6},
+///     Link {node1: 2, node2: 3, time: 1},
+/// ];
+/// let snapshots: Vec<_> = SnapshotIter::new(&mut links.into_iter(), 4, 2).collect();
+/// let bounds: Vec<_> = snapshots.iter().map(|s| (s.start, s.stop, s.nb_links())).collect();
+/// assert_eq!(bounds, vec![(6, 10, 3), (4, 8, 1), (2, 6, 0), (0, 4, 1)]);
+/// assert_eq!(snapshots[0].edges.get(&(0, 1)), Some(&2));
+/// assert_eq!(snapshots[0].components(), vec![vec![0, 1, 2]]);
+/// ```
+pub struct SnapshotIter<'a> {
+    iter: &'a mut LinkIterator,
+    window: Time,
+    step: Time,
+    stop: Option<Time>,
+    buffer: VecDeque<Link>,
+    next_link: Option<Link>,
+}
+
+impl<'a> SnapshotIter<'a> {
+    /// Decorate a LinkIterator for aggregating windows of duration `window` every `step`.
+    pub fn new(iterator: &'a mut LinkIterator, window: Time, step: Time) -> SnapshotIter<'a> {
+        assert!(window > 0 && step > 0, "Window and step must be positive");
+        let next_link = iterator.next();
+        SnapshotIter {
+            iter: iterator,
+            window,
+            step,
+            stop: next_link.map(|link| link.time + 1),
+            buffer: VecDeque::new(),
+            next_link,
+        }
+    }
+}
+
+impl<'a> Iterator for SnapshotIter<'a> {
+    type Item = Snapshot;
+    fn next(&mut self) -> Option<Snapshot> {
+        let stop = self.stop?;
+        if self.buffer.is_empty() && self.next_link.is_none() { return None; }
+        let start = stop.saturating_sub(self.window);
+        while let Some(link) = self.next_link {
+            if link.time < start { break; }
+            // links between two windows when step > window
+            if link.time < stop { self.buffer.push_back(link); }
+            self.next_link = self.iter.next();
+        }
+        let mut edges: BTreeMap<(Node, Node), usize> = BTreeMap::new();
+        for link in &self.buffer {
+            let pair = if link.node1 <= link.node2 { (link.node1, link.node2) } else { (link.node2, link.node1) };
+            *edges.entry(pair).or_insert(0) += 1;
+        }
+        self.stop = if start == 0 { None } else { Some(stop.saturating_sub(self.step)) };
+        let next_stop = self.stop.unwrap_or(0);
+        while self.buffer.front().is_some_and(|link| link.time >= next_stop) {
+            self.buffer.pop_front();
+        }
+        Some(Snapshot { start, stop, edges })
+    }
+}
+
+// /////////////
+// / TEST ZONE /
+// /////////////
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    fn snapshot_gap_test() {
+        let links = vec![
+            Link {node1: 0, node2: 1, time: 9},
+            Link {node1: 1, node2: 2, time: 6},
+            Link {node1: 2, node2: 3, time: 4},
+            Link {node1: 3, node2: 3, time: 3},
+        ];
+        let snapshots: Vec<Snapshot> = SnapshotIter::new(&mut links.into_iter(), 2, 5).collect();
+        let bounds: Vec<(Time, Time, usize)> = snapshots.iter().map(|s| (s.start, s.stop, s.nb_links())).collect();
+        assert_eq!(vec![(8, 10, 1), (3, 5, 2)], bounds);
+        assert_eq!(1, snapshots[1].nb_edges());
+        assert_eq!(1., snapshots[1].density());
+    }
+}
@@ -1,59 +1,49 @@
-use data::link::{Link, Node, Time};
-// use data::iterators::link_iterator::*;
-use data::rand::thread_rng;
-use data::rand::Rng;
+use data::link::{Link, Time};
+use data::iterators::link_generator::*;
+use data::rand::rngs::StdRng;
 
 
+/// Generate a linkstream where each pair of nodes is linked at each time with probability `proba`.
+///
+/// The times of each pair are drawn with `bernoulli_times`, so that a sparse stream costs its
+/// number of links rather than its number of pairs times its duration.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::iterators::uniform_link_generator::UnifLinkGenerator;
+/// # use linkstreams::data::iterators::link_generator::rng_from_seed;
+/// let links: Vec<_> = UnifLinkGenerator::new(3, 1, 1., rng_from_seed(Some(0))).collect();
+/// let links: Vec<_> = links.iter().map(|link| (link.node1, link.node2, link.time)).collect();
+/// assert_eq!(links, vec![(0, 1, 1), (0, 2, 1), (1, 2, 1), (0, 1, 0), (0, 2, 0), (1, 2, 0)]);
+/// ```
 pub struct UnifLinkGenerator {
     nb_nodes: usize,
-    proba: f64,
     time_max: Time,
-    time: Time,
-    node1: Node,
-    node2: Node,
+    links: ::std::vec::IntoIter<Link>,
 }
 
 impl UnifLinkGenerator {
-    pub fn new(nb_nodes: usize, time_max: Time, proba: f64) -> UnifLinkGenerator {
-        UnifLinkGenerator {
-            nb_nodes: nb_nodes,
-            proba: 1. / proba,
-            time_max: time_max,
-            time: time_max,
-            node1: 0,
-            node2: 1,
-        }
-    }
-    fn current_link(&self) -> Link {
-        Link {
-            node1: self.node1,
-            node2: self.node2,
-            time: self.time,
+    pub fn new(nb_nodes: usize, time_max: Time, proba: f64, mut rng: StdRng) -> UnifLinkGenerator {
+        assert!((0. ..=1.).contains(&proba), "The probability must be between 0 and 1");
+        let mut links: Vec<Link> = Vec::new();
+        for node1 in 0..nb_nodes {
+            for node2 in node1 + 1..nb_nodes {
+                let times = bernoulli_times(proba, time_max, &mut rng);
+                links.extend(times.into_iter().map(|time| Link { node1, node2, time }));
+            }
         }
+        UnifLinkGenerator { nb_nodes, time_max, links: sorted_links(links) }
     }
 }
 
+impl LinkGenerator for UnifLinkGenerator {
+    fn nb_nodes(&self) -> usize { self.nb_nodes }
+    fn time_max(&self) -> Time { self.time_max }
+}
 
 impl Iterator for UnifLinkGenerator {
     type Item = Link;
     fn next(&mut self) -> Option<Link> {
-        while self.time <= self.time_max {
-            while self.node1 < self.nb_nodes {
-                while self.node2 < self.nb_nodes {
-                    let link = self.current_link();
-                    self.node2 = self.node2 + 1;
-                    let rnf : f64 = thread_rng().gen();
-                    if rnf <= self.proba {
-                        return Some(link);
-                    }
-                }
-                self.node1 = self.node1 + 1;
-                self.node2 = self.node1 + 1;
-            }
-            self.time = self.time - 1;
-            self.node1 = 0;
-            self.node2 = 1;
-        }
-        None
+        self.links.next()
     }
 }
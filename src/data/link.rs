@@ -58,14 +58,16 @@ impl FromStr for Link {
     /// # use linkstreams::data::link::Link;
     /// # use std::str::FromStr;
     /// assert_eq!(Link::from_str("0 1 10").unwrap(), Link {node1: 0, node2: 1, time: 10});
+    /// assert!(Link::from_str("0 1").is_err());
+    /// assert!(Link::from_str("0 a 10").is_err());
     /// ```
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         let data: Vec<&str> = line.split(" ").collect();
-        assert!(data.len() >= 3, "Error in line, not representing a link !");
+        if data.len() < 3 { return Err(LinkParseError); }
         Ok(Link {
-            node1: data[0].parse::<usize>().unwrap(),
-            node2: data[1].parse::<usize>().unwrap(),
-            time: data[2].parse::<u64>().unwrap(),
+            node1: data[0].parse::<usize>().map_err(|_| LinkParseError)?,
+            node2: data[1].parse::<usize>().map_err(|_| LinkParseError)?,
+            time: data[2].parse::<u64>().map_err(|_| LinkParseError)?,
         })
     }
 }
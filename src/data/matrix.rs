@@ -1,6 +1,7 @@
 use data::link::Time;
 use data::link::Node;
 use data::filtre::*;
+use data::sparse_matrix::SparseMatrix;
 use std::cmp::min;
 /// A simple parametrized matrix implementation.
 #[derive(Debug)]
@@ -51,10 +52,8 @@ impl<V> Matrix<V>
         pub fn copy(&mut self, other: &Self) {
             assert_eq!(self.width, other.width);
             assert_eq!(self.height, other.height);
-            for x in 0..self.width {
-                for y in 00..self.height {
-                    self.set(x, y, other.get(x, y));
-                }
+            for (line, other_line) in self.matrix.iter_mut().zip(other.matrix.iter()) {
+                line.copy_from_slice(other_line);
             }
         }
 
@@ -312,6 +311,206 @@ impl Matrix<Time> {
     }
 }
 
+/// Matrices of the distances between nodes, `Time::MAX` meaning that a node is not reachable.
+pub trait DistMatrix {
+    /// Number of nodes
+    fn size(&self) -> usize;
+    fn get(&self, x: Node, y: Node) -> Time;
+    fn set(&mut self, x: Node, y: Node, time: Time);
+    /// Set the distance of each node to itself
+    fn diag(&mut self, time: Time);
+    fn copy(&mut self, other: &Self);
+    /// Copy the row `x` of `other`
+    fn copy_row(&mut self, other: &Self, x: Node);
+    /// Call `f` on the nodes reachable from `x` with their distance, `Time::MAX` entries may be omitted.
+    fn for_each_in_row<F: FnMut(Node, Time)>(&self, x: Node, f: F);
+
+    /// Update the distances with a link between `u` and `v` at time `t`, `previous` being
+    /// the distances at the previous time of the stream.
+    fn add_link(&mut self, previous: &Self, u: Node, v: Node, t: Time) where Self: Sized {
+        self.set(v, u, t);
+        self.set(u, v, t);
+        // only the nodes reachable from u or v in the previous distances can get closer
+        previous.for_each_in_row(u, |i, time| {
+            if i != u && i != v && time > t && time < self.get(v, i) { self.set(v, i, time); }
+        });
+        previous.for_each_in_row(v, |i, time| {
+            if i != u && i != v && time > t && time < self.get(u, i) { self.set(u, i, time); }
+        });
+    }
+
+    /// Tells if the induced matrix is a delta-clique at time `time`, see `Matrix::is_subset_delta_clique`.
+    fn is_subset_delta_clique(&self, time: Time, delta: Time, nfilter: &NodeFilter) -> bool {
+        let nodes: Vec<Node> = (0..self.size()).filter(|&x| nfilter(x)).collect();
+        nodes.iter().all(|&x| {
+            let mut count = 0;
+            self.for_each_in_row(x, |y, t| if nfilter(y) && t - time <= delta { count += 1; });
+            count == nodes.len()
+        })
+    }
+}
+
+impl DistMatrix for Matrix<Time> {
+    fn size(&self) -> usize { self.width }
+    fn get(&self, x: Node, y: Node) -> Time { Matrix::get(self, x, y) }
+    fn set(&mut self, x: Node, y: Node, time: Time) { Matrix::set(self, x, y, time) }
+    fn diag(&mut self, time: Time) { Matrix::diag(self, time) }
+    fn copy(&mut self, other: &Self) { Matrix::copy(self, other) }
+    fn copy_row(&mut self, other: &Self, x: Node) { self.matrix[x].copy_from_slice(&other.matrix[x]) }
+
+    fn for_each_in_row<F: FnMut(Node, Time)>(&self, x: Node, mut f: F) {
+        for (y, &time) in self.matrix[x].iter().enumerate() {
+            if time < Time::MAX { f(y, time); }
+        }
+    }
+
+    fn is_subset_delta_clique(&self, time: Time, delta: Time, nfilter: &NodeFilter) -> bool {
+        Matrix::is_subset_delta_clique(self, time, delta, nfilter)
+    }
+}
+
+/// Greatest number of nodes for which distances are stored in a dense matrix.
+pub const DENSE_MAX_NODES: usize = 4096;
+
+/// Distance matrix which is dense for at most `DENSE_MAX_NODES` nodes and sparse above.
+///
+/// A dense matrix takes `8 n²` bytes, which does not fit in memory for large networks,
+/// while most pairs of nodes of such networks do not reach each other within delta.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::matrix::*;
+/// use linkstreams::data::link::Time;
+/// let mut dist = Distances::new(10000);
+/// assert!(!dist.is_dense());
+/// dist.set(0, 9999, 5);
+/// assert_eq!(dist.get(0, 9999), 5);
+/// assert_eq!(dist.get(9999, 0), Time::MAX);
+/// assert!(Distances::new(10).is_dense());
+/// ```
+#[derive(Debug)]
+pub enum Distances {
+    Dense(Matrix<Time>),
+    Sparse(SparseMatrix),
+}
+
+impl Distances {
+    /// Create a matrix of `size` unreachable nodes, dense or sparse depending on `size`.
+    pub fn new(size: usize) -> Distances {
+        if size <= DENSE_MAX_NODES { Distances::Dense(Matrix::new(Time::MAX, size, size)) }
+        else { Distances::Sparse(SparseMatrix::new(size)) }
+    }
+
+    pub fn is_dense(&self) -> bool {
+        match *self { Distances::Dense(_) => true, Distances::Sparse(_) => false }
+    }
+}
+
+impl DistMatrix for Distances {
+    fn size(&self) -> usize {
+        match *self { Distances::Dense(ref m) => m.width, Distances::Sparse(ref m) => m.size() }
+    }
+
+    fn get(&self, x: Node, y: Node) -> Time {
+        match *self { Distances::Dense(ref m) => DistMatrix::get(m, x, y), Distances::Sparse(ref m) => m.get(x, y) }
+    }
+
+    fn set(&mut self, x: Node, y: Node, time: Time) {
+        match *self { Distances::Dense(ref mut m) => DistMatrix::set(m, x, y, time), Distances::Sparse(ref mut m) => m.set(x, y, time) }
+    }
+
+    fn diag(&mut self, time: Time) {
+        match *self { Distances::Dense(ref mut m) => DistMatrix::diag(m, time), Distances::Sparse(ref mut m) => m.diag(time) }
+    }
+
+    fn copy(&mut self, other: &Self) {
+        match (self, other) {
+            (&mut Distances::Dense(ref mut m), Distances::Dense(o)) => DistMatrix::copy(m, o),
+            (&mut Distances::Sparse(ref mut m), Distances::Sparse(o)) => m.copy(o),
+            _ => panic!("Copy between dense and sparse distances"),
+        }
+    }
+
+    fn copy_row(&mut self, other: &Self, x: Node) {
+        match (self, other) {
+            (&mut Distances::Dense(ref mut m), Distances::Dense(o)) => m.copy_row(o, x),
+            (&mut Distances::Sparse(ref mut m), Distances::Sparse(o)) => m.copy_row(o, x),
+            _ => panic!("Copy between dense and sparse distances"),
+        }
+    }
+
+    fn add_link(&mut self, previous: &Self, u: Node, v: Node, t: Time) {
+        match (self, previous) {
+            (&mut Distances::Dense(ref mut m), Distances::Dense(p)) => m.add_link(p, u, v, t),
+            (&mut Distances::Sparse(ref mut m), Distances::Sparse(p)) => m.add_link(p, u, v, t),
+            _ => panic!("Update between dense and sparse distances"),
+        }
+    }
+
+    fn for_each_in_row<F: FnMut(Node, Time)>(&self, x: Node, f: F) {
+        match *self { Distances::Dense(ref m) => m.for_each_in_row(x, f), Distances::Sparse(ref m) => m.for_each_in_row(x, f) }
+    }
+
+    fn is_subset_delta_clique(&self, time: Time, delta: Time, nfilter: &NodeFilter) -> bool {
+        match *self {
+            Distances::Dense(ref m) => DistMatrix::is_subset_delta_clique(m, time, delta, nfilter),
+            Distances::Sparse(ref m) => m.is_subset_delta_clique(time, delta, nfilter),
+        }
+    }
+}
+
+/// Graphs of a relation between nodes, such as delta-reachability graphs.
+pub trait ReachGraph {
+    /// Number of nodes
+    fn size(&self) -> usize;
+    /// Tells if `x` is in relation with `y`
+    fn reaches(&self, x: Node, y: Node) -> bool;
+
+    fn successors(&self, node: Node) -> Vec<Node> {
+        (0..self.size()).filter(|&y| self.reaches(node, y)).collect()
+    }
+
+    fn predecessors(&self, node: Node) -> Vec<Node> {
+        (0..self.size()).filter(|&x| self.reaches(x, node)).collect()
+    }
+
+    /// Tells if the graph induced by the subset is a clique, see `Matrix::is_subset_clique`.
+    fn is_subset_clique(&self, nfilter: &NodeFilter) -> bool {
+        let nodes: Vec<Node> = (0..self.size()).filter(|&x| nfilter(x)).collect();
+        nodes.iter().all(|&x| nodes.iter().all(|&y| self.reaches(x, y)))
+    }
+
+    /// Node of the subset with the lowest degree, the last one in case of ties, as `Matrix::get_max_deg_node`.
+    fn get_min_deg_node(&self, nfilter: &NodeFilter) -> Node {
+        let nodes: Vec<Node> = (0..self.size()).filter(|&x| nfilter(x)).collect();
+        let mut min_node = 0;
+        let mut min_deg = Node::MAX;
+        for &x in &nodes {
+            let deg = nodes.iter().filter(|&&y| self.reaches(x, y)).count()
+                + nodes.iter().filter(|&&y| self.reaches(y, x)).count();
+            if deg <= min_deg {
+                min_deg = deg;
+                min_node = x;
+            }
+        }
+        min_node
+    }
+}
+
+impl ReachGraph for Matrix<Time> {
+    fn size(&self) -> usize { self.width }
+
+    fn reaches(&self, x: Node, y: Node) -> bool {
+        let value = Matrix::get(self, x, y);
+        value != 0 && value < Time::MAX
+    }
+
+    fn successors(&self, node: Node) -> Vec<Node> { Matrix::successors(self, node) }
+    fn predecessors(&self, node: Node) -> Vec<Node> { Matrix::predecessors(self, node) }
+    fn is_subset_clique(&self, nfilter: &NodeFilter) -> bool { Matrix::is_subset_clique(self, nfilter) }
+    fn get_min_deg_node(&self, nfilter: &NodeFilter) -> Node { Matrix::get_max_deg_node(self, nfilter) }
+}
+
 // /////////////
 // / TEST ZONE /
 // /////////////
@@ -1,6 +1,8 @@
 extern crate rand;
 
 pub mod matrix;
+pub mod sparse_matrix;
+pub mod bitset;
 pub mod link;
 pub mod filtre;
 pub mod iterators;
@@ -0,0 +1,117 @@
+use data::link::{Node, Time};
+use data::matrix::DistMatrix;
+use std::collections::HashMap;
+
+/// A square matrix of times stored as one hash map per row, absent entries being `Time::MAX`.
+///
+/// Memory is linear in the number of reachable pairs instead of quadratic in the number of nodes.
+///
+/// # Example
+/// ```
+/// # use linkstreams::data::sparse_matrix::SparseMatrix;
+/// # use linkstreams::data::matrix::DistMatrix;
+/// use linkstreams::data::link::Time;
+/// let mut m = SparseMatrix::new(3);
+/// m.diag(4);
+/// m.set(0, 2, 7);
+/// assert_eq!(m.get(0, 2), 7);
+/// assert_eq!(m.get(2, 0), Time::MAX);
+/// let mut row = Vec::new();
+/// m.for_each_in_row(0, |y, time| row.push((y, time)));
+/// row.sort();
+/// assert_eq!(row, vec![(0, 4), (2, 7)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseMatrix {
+    rows: Vec<HashMap<Node, Time>>,
+}
+
+impl SparseMatrix {
+    /// Create a `size`x`size` matrix filled with `Time::MAX`
+    pub fn new(size: usize) -> SparseMatrix {
+        SparseMatrix { rows: vec![HashMap::new(); size] }
+    }
+
+    /// Number of stored entries
+    pub fn len(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(|row| row.is_empty())
+    }
+}
+
+impl DistMatrix for SparseMatrix {
+    fn size(&self) -> usize { self.rows.len() }
+
+    fn get(&self, x: Node, y: Node) -> Time {
+        assert!(y < self.rows.len(), "Vector out of range reading!");
+        self.rows[x].get(&y).cloned().unwrap_or(Time::MAX)
+    }
+
+    fn set(&mut self, x: Node, y: Node, time: Time) {
+        assert!(y < self.rows.len(), "Vector out of range writting!");
+        if time == Time::MAX { self.rows[x].remove(&y); } else { self.rows[x].insert(y, time); }
+    }
+
+    fn diag(&mut self, time: Time) {
+        for x in 0..self.rows.len() { self.set(x, x, time); }
+    }
+
+    fn copy(&mut self, other: &Self) {
+        assert_eq!(self.rows.len(), other.rows.len());
+        self.rows.clone_from(&other.rows);
+    }
+
+    fn copy_row(&mut self, other: &Self, x: Node) {
+        self.rows[x].clone_from(&other.rows[x]);
+    }
+
+    fn for_each_in_row<F: FnMut(Node, Time)>(&self, x: Node, mut f: F) {
+        for (&y, &time) in &self.rows[x] { f(y, time); }
+    }
+}
+
+// /////////////
+// / TEST ZONE /
+// /////////////
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use algo;
+    use data::link::Link;
+    use data::matrix::{ReachGraph, DENSE_MAX_NODES};
+    use data::iterators::link_generator::rng_from_seed;
+    use data::iterators::uniform_link_generator::UnifLinkGenerator;
+
+    #[test]
+    fn sparse_distances_test() {
+        let (nb_nodes, delta) = (10, 15);
+        let links: Vec<Link> = UnifLinkGenerator::new(nb_nodes, 300, 0.1, rng_from_seed(Some(1))).collect();
+        let dense = algo::delta_reachability_graph(&mut links.clone().into_iter(), delta, nb_nodes, &|_| true, &|_| true);
+        let sparse = algo::delta_reachability_graph(&mut links.clone().into_iter(), delta, DENSE_MAX_NODES + 1, &|_| true, &|_| true);
+        for x in 0..nb_nodes {
+            for y in 0..nb_nodes {
+                assert_eq!(dense.reaches(x, y), sparse.reaches(x, y));
+            }
+        }
+        let nfilter = move |node| node < nb_nodes;
+        let dense: Vec<_> = algo::is_delta_connected(&mut links.clone().into_iter(), delta, nb_nodes, &nfilter, &|_| true).collect();
+        let sparse: Vec<_> = algo::is_delta_connected(&mut links.into_iter(), delta, DENSE_MAX_NODES + 1, &nfilter, &|_| true).collect();
+        assert!(dense.iter().any(|&(_, connected)| connected));
+        assert_eq!(dense, sparse);
+    }
+
+    #[test]
+    fn sparse_set_test() {
+        let mut m = SparseMatrix::new(4);
+        assert!(m.is_empty());
+        m.diag(3);
+        m.set(1, 2, 5);
+        assert_eq!(m.len(), 5);
+        m.set(1, 2, Time::MAX);
+        assert_eq!(m.len(), 4);
+        assert_eq!(m.get(1, 2), Time::MAX);
+    }
+}
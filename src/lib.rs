@@ -1,3 +1,5 @@
 pub mod data;
 extern crate rand;
+extern crate serde;
+extern crate rayon;
 pub mod algo;
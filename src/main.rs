@@ -1,45 +1,118 @@
 extern crate serde;
+extern crate serde_json;
 extern crate docopt;
 extern crate linkstreams;
+extern crate rayon;
 
 use docopt::Docopt;
+use std::io::BufRead;
+use std::io::Write;
 use serde::{Serialize, Deserialize};
 
 use linkstreams::*;
 use linkstreams::data::link::Time;
 use linkstreams::data::link::Node;
 use linkstreams::data::iterators::*;
+use linkstreams::data::filtre::TimeFilter;
+use linkstreams::data::filtre::NodeFilter;
 
 static USAGE: &'static str = "
 Usage:
        linkstream calc connexity <delta> <nbNodes> [node <node>... | time <start> <stop> | both <start> <stop> <node>...]
-       linkstream calc comps [up] <delta> <nbNodes> [node <node>... | both <start> <stop> <node>...]
-       linkstream calc exist [lr | cut] <delta> <nbNodes>
-       linkstream calc part [up] <delta> <nbNodes>
-       linkstream calc infix [up] <delta> <nbNodes> <proba>
+       linkstream calc comps [up | exact [--budget <n>]] <delta> <nbNodes> [node <node>... | both <start> <stop> <node>...]
+       linkstream calc exist [lr [--unordered [--min-duration <d>]] | cut | boxes [--top <k>] [--min-nodes <m>]] <delta> <nbNodes>
+       linkstream calc part [up] <delta> <nbNodes> [--threads <n>]
+       linkstream calc infix [up] <delta> <nbNodes> <proba> [--threads <n>]
+       linkstream calc sweep <dmin> <dmax> <step> (cut | lr | boxes) <nbNodes>
+       linkstream calc cliques <delta>
+       linkstream calc motifs [directed] <delta>
+       linkstream calc triangles [nodes] <delta>
+       linkstream calc cores <delta>
+       linkstream calc communities <window> [<step>] [--threshold <j>]
        linkstream rename
-       linkstream gen <nbNodes> <stop> <proba>
+       linkstream shuffle --model <m> [--seed <s>]
+       linkstream check [<nbNodes>] [--delta <d>]
+       linkstream aggregate [stats] <window> [<step>]
+       linkstream gen activity <nbNodes> <stop> [--gamma <g>] [--epsilon <e>] [--links <m>] [--seed <s>]
+       linkstream gen (poisson | hawkes) <nbNodes> <stop> [--rate <r>] [--alpha <a>] [--decay <b>] [--seed <s>]
+       linkstream gen communities <nbNodes> <stop> <delta> [--groups <k>] [--noise <p>] [--seed <s>]
+       linkstream gen planted <nbNodes> <stop> <delta> --truth <file> [--groups <k>] [--noise <p>] [--seed <s>]
+       linkstream gen <nbNodes> <stop> <proba> [--seed <s>]
        linkstream info (count (node | links) | degrees <nbNodes> | repart <nbNodes>)
+       linkstream info degrees <nbNodes> --delta <d> [dist | density]
+       linkstream info intercontact (pairs | nodes) [hist [--base <b>]]
+       linkstream info activity <bin> [--delta <d>]
+       linkstream info summary [--format <f>]
+       linkstream simulate (si | sis | sir) --beta <p> --seeds <nodes> [--recovery <d>] [--runs <r>] [--seed <s>] [nodes]
+       linkstream score (comps [up] | lr | part [up]) <delta> <nbNodes> <truth> [--threads <n>]
        linkstream filter (node <node>... | time <start> <stop> | both <start> <stop> <node>...)
+
+Options:
+       --budget <n>  Maximum number of search steps of exact components [default: 1000000].
+       --threads <n>  Number of threads computing the partitions, 0 for one per core [default: 0].
+       --top <k>  Only output the k largest boxes, 0 for all of them [default: 0].
+       --min-nodes <m>  Minimum number of nodes of the boxes [default: 1].
+       --format <f>  Output format, text or json [default: text].
+       --delta <d>  Delta of the degrees, of the activity smoothing or of the arithmetic checks. The density is a per-event average.
+       --base <b>  Base of the logarithmic bins of histograms [default: 2].
+       --unordered  Use the bitset sweep for the largest rectangle.
+       --min-duration <d>  Largest set of nodes existing together at least d.
+       --beta <p>  Probability of infection at each link with an infected node.
+       --seeds <nodes>  Comma separated nodes infected at the start.
+       --recovery <d>  Duration after which infected nodes recover.
+       --runs <r>  Number of averaged runs [default: 1].
+       --seed <s>  Seed of the random number generator, random if not given.
+       --gamma <g>  Exponent of the power law of the activities [default: 2.1].
+       --epsilon <e>  Minimal activity [default: 0.001].
+       --links <m>  Number of links created by an active node [default: 1].
+       --rate <r>  Base rate of the events of each pair of nodes [default: 0.001].
+       --alpha <a>  Excitation of the rate by each event of a hawkes process [default: 0.5].
+       --decay <b>  Decay rate of the excitation of a hawkes process [default: 1].
+       --groups <k>  Number of planted communities or cliques [default: 2].
+       --noise <p>  Probability of links between communities or of background links at each time [default: 0].
+       --truth <file>  File where the planted cliques are written, one `start stop n...` per line.
+       --model <m>  Null model: timestamps, sequences, shifts or swaps.
+       --threshold <j>  Minimum Jaccard index for a community to keep its id [default: 0.3].
 ";
 
 #[allow(non_snake_case)]
 #[derive(Serialize, Deserialize, Debug)]
 struct Args {
     cmd_rename: bool,
+    cmd_check: bool,
+    cmd_aggregate: bool,
+    cmd_stats: bool,
     cmd_gen: bool,
     cmd_info: bool,
     cmd_count: bool,
     cmd_degrees: bool,
+    cmd_dist: bool,
+    cmd_density: bool,
     cmd_repart: bool,
+    cmd_intercontact: bool,
+    cmd_pairs: bool,
+    cmd_nodes: bool,
+    cmd_hist: bool,
+    cmd_activity: bool,
+    cmd_summary: bool,
+    cmd_simulate: bool,
     cmd_filter: bool,
     cmd_calc: bool,
     cmd_connexity: bool,
     cmd_comps: bool,
     cmd_up: bool,
+    cmd_exact: bool,
     cmd_exist: bool,
     cmd_part: bool,
     cmd_infix: bool,
+    cmd_sweep: bool,
+    cmd_boxes: bool,
+    cmd_cliques: bool,
+    cmd_motifs: bool,
+    cmd_triangles: bool,
+    cmd_cores: bool,
+    cmd_communities: bool,
+    cmd_directed: bool,
     cmd_lr: bool,
     cmd_cut: bool,
     cmd_node: bool,
@@ -52,6 +125,45 @@ struct Args {
     arg_nbNodes: String,
     arg_delta: String,
     arg_proba: String,
+    arg_dmin: String,
+    arg_dmax: String,
+    arg_step: String,
+    arg_window: String,
+    arg_bin: String,
+    flag_budget: String,
+    flag_threads: String,
+    flag_top: String,
+    flag_min_nodes: String,
+    flag_base: String,
+    flag_format: String,
+    flag_threshold: String,
+    flag_beta: String,
+    flag_seeds: String,
+    flag_recovery: Option<String>,
+    flag_runs: String,
+    flag_seed: Option<String>,
+    cmd_shuffle: bool,
+    flag_model: String,
+    cmd_poisson: bool,
+    cmd_hawkes: bool,
+    flag_gamma: String,
+    flag_epsilon: String,
+    flag_links: String,
+    flag_rate: String,
+    flag_alpha: String,
+    flag_decay: String,
+    flag_groups: String,
+    flag_noise: String,
+    cmd_planted: bool,
+    flag_truth: String,
+    cmd_score: bool,
+    arg_truth: String,
+    cmd_si: bool,
+    cmd_sis: bool,
+    cmd_sir: bool,
+    flag_delta: Option<String>,
+    flag_unordered: bool,
+    flag_min_duration: Option<String>,
 }
 
 #[allow(non_snake_case)]
@@ -66,8 +178,9 @@ fn main() {
     let mut delta: Option<Time> = None;
     let mut nodes: Option<Vec<Node>> = None;
     let mut proba: Option<u64> = None;
+    let mut deltas: Option<Vec<Time>> = None;
     let mut stdinLinks = stdin_link_iterator::StdinLinkIter::new();
-    if args.cmd_calc || args.cmd_degrees || args.cmd_repart || args.cmd_gen || args.cmd_infix {
+    if (args.cmd_calc && !args.cmd_cliques && !args.cmd_motifs && !args.cmd_triangles && !args.cmd_cores && !args.cmd_communities) || args.cmd_degrees || args.cmd_repart || args.cmd_gen || args.cmd_infix || args.cmd_score {
         nbNodes = Some(usize::from_str_radix(&args.arg_nbNodes, 10).unwrap());
     }
     if args.cmd_time || args.cmd_both {
@@ -81,32 +194,128 @@ fn main() {
     }
     if args.cmd_gen {
         stop = Some(Time::from_str_radix(&args.arg_stop, 10).unwrap());
-        proba = Some(u64::from_str_radix(&args.arg_proba, 10).unwrap());
     }
-    if args.cmd_calc {
+    if (args.cmd_calc && !args.cmd_sweep && !args.cmd_communities) || args.cmd_score {
         delta = Some(Time::from_str_radix(&args.arg_delta, 10).unwrap());
     }
+    if args.cmd_sweep {
+        let dmin = args.arg_dmin.parse::<Time>().unwrap();
+        let dmax = args.arg_dmax.parse::<Time>().unwrap();
+        let step = args.arg_step.parse::<usize>().unwrap();
+        assert!(step > 0, "The sweep step must be positive");
+        deltas = Some((dmin..dmax + 1).step_by(step).collect());
+    }
+    let threads = args.flag_threads.parse::<usize>().unwrap();
+    if threads > 0 {
+        rayon::ThreadPoolBuilder::new().num_threads(threads).build_global().expect("Failed to start the thread pool");
+    }
+    let seed: Option<u64> = args.flag_seed.as_ref().map(|s| s.parse::<u64>().unwrap());
     if args.cmd_infix {
         proba = Some(u64::from_str_radix(&args.arg_proba, 10).unwrap());
 
     }
+    // AGGREGATE
+    if args.cmd_aggregate {
+        let window = args.arg_window.parse::<Time>().unwrap();
+        let step = if args.arg_step.is_empty() { window } else { args.arg_step.parse::<Time>().unwrap() };
+        let snapshots = snapshot_iterator::SnapshotIter::new(&mut stdinLinks, window, step);
+        for snapshot in snapshots {
+            // STATS
+            if args.cmd_stats {
+                let components = snapshot.components();
+                let maxcomp = components.iter().map(|comp| comp.len()).max().unwrap_or(0);
+                println!("{} {} {} {} {} {} {} {}", snapshot.start, snapshot.stop,
+                         snapshot.nodes().len(), snapshot.nb_edges(), snapshot.nb_links(),
+                         snapshot.density(), components.len(), maxcomp);
+            }
+            // EDGES
+            else {
+                for (&(u, v), count) in &snapshot.edges {
+                    println!("{} {} {} {} {}", snapshot.start, snapshot.stop, u, v, count);
+                }
+            }
+        }
+    }
+    // CHECK
+    else if args.cmd_check {
+        let nbNodes = if args.arg_nbNodes.is_empty() { None } else { Some(args.arg_nbNodes.parse::<usize>().unwrap()) };
+        let delta = args.flag_delta.as_ref().map(|d| d.parse::<Time>().unwrap());
+        let stdin = std::io::stdin();
+        let lines = stdin.lock().lines().map(|line| line.expect("Failed to read line"));
+        let violations = algo::check::check(lines, nbNodes, delta);
+        for (number, violation) in &violations {
+            let location = number.map_or("stream".to_string(), |n| format!("line {}", n));
+            println!("{}: {} (fix: {})", location, violation, violation.fix());
+        }
+        if !violations.is_empty() { std::process::exit(1); }
+    }
     // RENAME
-    if args.cmd_rename {
+    else if args.cmd_rename {
         let stream = rename_link_iterator::RenameLinkIter::new(&mut stdinLinks);
         for link in stream {
             println!("{}", link.to_string());
         }
     }
+    // SHUFFLE
+    else if args.cmd_shuffle {
+        let model = args.flag_model.parse::<shuffle_link_iterator::NullModel>().expect("Unknown null model");
+        let mut rng = link_generator::rng_from_seed(seed);
+        let stream = shuffle_link_iterator::ShuffleLinkIter::new(&mut stdinLinks, model, &mut rng);
+        for link in stream {
+            println!("{}", link.to_string());
+        }
+    }
     // GEN
-    if args.cmd_gen {
+    else if args.cmd_gen {
         let nbNodes = nbNodes.unwrap();
         let stop = stop.unwrap();
-        let proba = proba.unwrap() as f64;
-        let stream = uniform_link_generator::UnifLinkGenerator::new(nbNodes, stop, proba);
+        let rng = link_generator::rng_from_seed(seed);
+        let stream: Box<dyn link_generator::LinkGenerator> =
+        // ACTIVITY
+        if args.cmd_activity {
+            let gamma = args.flag_gamma.parse::<f64>().unwrap();
+            let epsilon = args.flag_epsilon.parse::<f64>().unwrap();
+            let m = args.flag_links.parse::<usize>().unwrap();
+            Box::new(activity_link_generator::ActivityLinkGenerator::new(nbNodes, stop, gamma, epsilon, m, rng))
+        }
+        // POISSON | HAWKES
+        else if args.cmd_poisson || args.cmd_hawkes {
+            let rate = args.flag_rate.parse::<f64>().unwrap();
+            let process = if args.cmd_poisson { poisson_link_generator::PointProcess::Poisson { rate } }
+                          else {
+                              let alpha = args.flag_alpha.parse::<f64>().unwrap();
+                              let decay = args.flag_decay.parse::<f64>().unwrap();
+                              poisson_link_generator::PointProcess::Hawkes { rate, alpha, decay }
+                          };
+            Box::new(poisson_link_generator::PoissonLinkGenerator::new(nbNodes, stop, process, rng))
+        }
+        // COMMUNITIES
+        else if args.cmd_communities {
+            let delta = args.arg_delta.parse::<Time>().unwrap();
+            let groups = args.flag_groups.parse::<usize>().unwrap();
+            let noise = args.flag_noise.parse::<f64>().unwrap();
+            Box::new(community_link_generator::CommunityLinkGenerator::new(nbNodes, stop, delta, groups, noise, rng))
+        }
+        // PLANTED
+        else if args.cmd_planted {
+            let delta = args.arg_delta.parse::<Time>().unwrap();
+            let groups = args.flag_groups.parse::<usize>().unwrap();
+            let noise = args.flag_noise.parse::<f64>().unwrap();
+            let generator = planted_link_generator::PlantedLinkGenerator::new(nbNodes, stop, delta, groups, noise, rng);
+            let mut truth = std::fs::File::create(&args.flag_truth).expect("Failed to create the truth file");
+            for &(start, stop, ref nodes) in generator.planted() {
+                let nodes: Vec<String> = nodes.iter().map(|node| node.to_string()).collect();
+                writeln!(truth, "{} {} {}", start, stop, nodes.join(" ")).expect("Failed to write the truth file");
+            }
+            Box::new(generator)
+        }
+        else {
+            let proba = args.arg_proba.parse::<f64>().unwrap();
+            Box::new(uniform_link_generator::UnifLinkGenerator::new(nbNodes, stop, proba, rng))
+        };
         for link in stream {
             println!("{}", link.to_string());
         }
-
     }
     // Info
     else if args.cmd_info {
@@ -115,6 +324,36 @@ fn main() {
             let (nb_nodes, nb_links) = algo::count_nodes_and_links(&mut stdinLinks);
             if args.cmd_node {println!("{}", nb_nodes);} else {println!("{}", nb_links);}
         }
+        // DELTA-DEGREES
+        else if args.cmd_degrees && args.flag_delta.is_some() {
+            let nbNodes = nbNodes.unwrap();
+            let delta = args.flag_delta.as_ref().map(|d| d.parse::<Time>().unwrap()).unwrap();
+            // DENSITY
+            if args.cmd_density {
+                println!("{}", algo::stats::delta_density(&mut stdinLinks, delta, nbNodes));
+            }
+            // DIST
+            else if args.cmd_dist {
+                let mut dist: Vec<usize> = vec![0; nbNodes];
+                for (_, degrees) in algo::stats::DeltaDegrees::new(&mut stdinLinks, delta) {
+                    dist[0] += nbNodes.saturating_sub(degrees.len());
+                    for (_, degree) in degrees {
+                        if degree >= dist.len() { dist.resize(degree + 1, 0); }
+                        dist[degree] += 1;
+                    }
+                }
+                for (degree, count) in dist.iter().enumerate() {
+                    println!("{} {}", degree, count);
+                }
+            }
+            else {
+                for (time, degrees) in algo::stats::DeltaDegrees::new(&mut stdinLinks, delta) {
+                    for (node, degree) in degrees {
+                        println!("{} {} {}", time, node, degree);
+                    }
+                }
+            }
+        }
         // DEGREES
         else if args.cmd_degrees {
             let nbNodes = nbNodes.unwrap();
@@ -132,6 +371,71 @@ fn main() {
                 println!("{}: {} {}", i, first, last);
             }
         }
+        // SUMMARY
+        else if args.cmd_summary {
+            match args.flag_format.as_str() {
+                "text" | "json" => {}
+                format => docopt::Error::Usage(format!("Unknown format {}, expected text or json", format)).exit(),
+            }
+            let summary = algo::stats::summary(&mut stdinLinks);
+            if args.flag_format == "json" {
+                println!("{}", serde_json::to_string(&summary).unwrap());
+            } else {
+                println!("nodes {}", summary.nodes);
+                println!("links {}", summary.links);
+                println!("pairs {}", summary.pairs);
+                println!("t_min {}", summary.t_min);
+                println!("t_max {}", summary.t_max);
+                println!("decreasing {}", summary.decreasing);
+                println!("increasing {}", summary.increasing);
+                println!("duplicates {}", summary.duplicates);
+                println!("self_loops {}", summary.self_loops);
+                println!("max_id {}", summary.max_id);
+                println!("isolated {}", summary.isolated);
+                println!("degree_min {}", summary.degree_min);
+                println!("degree_median {}", summary.degree_median);
+                println!("degree_max {}", summary.degree_max);
+            }
+        }
+        // ACTIVITY
+        else if args.cmd_activity {
+            let bin = args.arg_bin.parse::<Time>().unwrap();
+            let delta = args.flag_delta.as_ref().map(|d| d.parse::<Time>().unwrap());
+            for a in algo::stats::activity(&mut stdinLinks, bin, delta) {
+                println!("{} {} {} {} {} {}", a.start, a.stop, a.links, a.nodes, a.pairs, a.new_nodes);
+            }
+        }
+        // INTERCONTACT
+        else if args.cmd_intercontact {
+            let (pairs, nodes) = algo::stats::inter_event_times(&mut stdinLinks);
+            // HIST
+            if args.cmd_hist {
+                let base = args.flag_base.parse::<Time>().unwrap();
+                let taus: Vec<Time> = if args.cmd_pairs {
+                    pairs.into_values().flatten().collect()
+                } else {
+                    nodes.into_values().flatten().collect()
+                };
+                for (low, high, count) in algo::stats::log_histogram(&taus, base) {
+                    let pdf = count as f64 / (high - low) as f64 / taus.len() as f64;
+                    println!("{} {} {} {}", low, high, count, pdf);
+                }
+            }
+            // PAIRS
+            else if args.cmd_pairs {
+                for ((u, v), taus) in pairs {
+                    println!("{} {} {} {} {} {}", u, v, taus.len(), algo::stats::mean(&taus),
+                             algo::stats::burstiness(&taus), algo::stats::memory_coefficient(&taus));
+                }
+            }
+            // NODES
+            else {
+                for (node, taus) in nodes {
+                    println!("{} {} {} {} {}", node, taus.len(), algo::stats::mean(&taus),
+                             algo::stats::burstiness(&taus), algo::stats::memory_coefficient(&taus));
+                }
+            }
+        }
     }
     // FILTER
     else if args.cmd_filter {
@@ -166,39 +470,151 @@ fn main() {
             }
         }
     }
+    // SIMULATE
+    else if args.cmd_simulate {
+        let model = if args.cmd_si { algo::spreading::Model::SI }
+                    else if args.cmd_sis { algo::spreading::Model::SIS }
+                    else { algo::spreading::Model::SIR };
+        let beta = args.flag_beta.parse::<f64>().unwrap();
+        assert!((0. ..=1.).contains(&beta), "The infection probability must be between 0 and 1");
+        let seeds: Vec<Node> = args.flag_seeds.split(',').map(|s| s.parse::<Node>().unwrap()).collect();
+        let recovery = args.flag_recovery.as_ref().map(|d| d.parse::<Time>().unwrap());
+        assert!(model == algo::spreading::Model::SI || recovery.is_some(), "SIS and SIR need a --recovery duration");
+        let runs = args.flag_runs.parse::<usize>().unwrap();
+        let mut rng = link_generator::rng_from_seed(seed);
+        let spreading = algo::spreading::simulate(&mut stdinLinks, model, beta, recovery.unwrap_or(0), &seeds, runs, &mut rng);
+        // NODES
+        if args.cmd_nodes {
+            for (node, ratio, time) in spreading.infections {
+                println!("{} {} {}", node, ratio, time);
+            }
+        }
+        else {
+            for (time, infected, recovered) in spreading.curve {
+                println!("{} {} {}", time, infected, recovered);
+            }
+        }
+    }
+    // SCORE
+    else if args.cmd_score {
+        let nbNodes = nbNodes.unwrap();
+        let delta = delta.unwrap();
+        let file = std::fs::File::open(&args.arg_truth).expect("Failed to open the truth file");
+        let truth: Vec<(Time, Time, Vec<Node>)> = std::io::BufReader::new(file).lines()
+            .map(|line| algo::benchmark::parse_box(&line.expect("Failed to read line")).expect("Invalid truth line"))
+            .collect();
+        let nodes: Vec<Node> = (0..nbNodes).collect();
+        let (precision, recall, jaccard) =
+        // COMPS
+        if args.cmd_comps {
+            let truth = algo::benchmark::components_truth(&truth);
+            let start = truth.iter().map(|&(start, _, _)| start).min().unwrap_or(0);
+            let stop = truth.iter().map(|&(_, stop, _)| stop).max().unwrap_or(0);
+            let (comps, restes) = if args.cmd_up {
+                algo::delta_components_upper(&mut stdinLinks, nbNodes, delta, &nodes, &|_| true)
+            } else {
+                algo::delta_components_lower(&mut stdinLinks, nbNodes, delta, &nodes, &|_| true)
+            };
+            let found: Vec<(Time, Time, Vec<Node>)> = comps.into_iter().chain(restes)
+                .filter(|comp| comp.len() > 1).map(|comp| (start, stop, comp)).collect();
+            algo::benchmark::score(&found, &truth, true)
+        }
+        // LR
+        else if args.cmd_lr {
+            let (start, stop, vec) = algo::largest_boxe(&mut stdinLinks, &nodes, delta);
+            algo::benchmark::score(&[(start, stop + delta, vec)], &algo::benchmark::rectangle_truth(&truth), false)
+        }
+        // PART
+        else {
+            let parts: Vec<(Time, Time, Vec<Vec<Node>>)> = algo::delta_partition(&mut stdinLinks, &nodes, delta, args.cmd_up)
+                .into_iter().map(|(start, stop, (mut comps, restes))| { comps.extend(restes); (start, stop, comps) })
+                .collect();
+            algo::benchmark::score(&algo::benchmark::partition_boxes(&parts, delta), &truth, true)
+        };
+        println!("{} {} {}", precision, recall, jaccard);
+    }
+    // SWEEP
+    else if args.cmd_sweep {
+        let nodes: Vec<usize> = (0..nbNodes.unwrap()).collect();
+        let trace = algo::LastSeenTrace::new(&mut stdinLinks, &nodes);
+        for delta in deltas.unwrap() {
+            let existence = trace.existence(delta);
+            // CUT
+            if args.cmd_cut {
+                println!("{} {}", delta, algo::intervals_from_trace(&existence).len());
+            }
+            // LR
+            else if args.cmd_lr {
+                let (start, stop, vec) = algo::largest_boxe_from_trace(&existence);
+                println!("{} {} {} {} {} {:?}", delta, start, stop, (stop-start)*(vec.len() as Time), vec.len(), vec);
+            }
+            // BOXES
+            else if args.cmd_boxes {
+                let intervals = algo::intervals_from_trace(&existence);
+                println!("{} {}", delta, algo::mean_box_size(&intervals, delta));
+            }
+        }
+    }
+    // CLIQUES
+    else if args.cmd_cliques {
+        for (start, stop, set) in algo::cliques::DeltaCliques::new(&mut stdinLinks, delta.unwrap()) {
+            let set_str: Vec<String> = set.iter().map(|node| format!("{}", node)).collect();
+            println!("{} {} {}", start, stop, set_str.join(" "));
+        }
+    }
+    // MOTIFS
+    else if args.cmd_motifs {
+        let motifs = algo::motifs::temporal_motifs(&mut stdinLinks, delta.unwrap(), args.cmd_directed);
+        for (motif, count) in motifs {
+            println!("{} {}", algo::motifs::motif_to_string(&motif, args.cmd_directed), count);
+        }
+    }
+    // TRIANGLES
+    else if args.cmd_triangles {
+        for (time, triangles, clustering) in algo::delta_triangles(&mut stdinLinks, delta.unwrap()) {
+            if args.cmd_nodes {
+                for (node, coefficient) in clustering {
+                    println!("{} {} {}", time, node, coefficient);
+                }
+            }
+            else {
+                let mean = clustering.iter().map(|&(_, coefficient)| coefficient).sum::<f64>() / clustering.len() as f64;
+                println!("{} {} {}", time, triangles, mean);
+            }
+        }
+    }
+    // CORES
+    else if args.cmd_cores {
+        for (start, stop, node, core) in algo::delta_cores(&mut stdinLinks, delta.unwrap()) {
+            println!("{} {} {} {}", start, stop, node, core);
+        }
+    }
+    // COMMUNITIES
+    else if args.cmd_communities {
+        let window = args.arg_window.parse::<Time>().unwrap();
+        let step = if args.arg_step.is_empty() { window } else { args.arg_step.parse::<Time>().unwrap() };
+        let threshold = args.flag_threshold.parse::<f64>().unwrap();
+        for (start, stop, id, set) in algo::communities::temporal_communities(&mut stdinLinks, window, step, threshold) {
+            let set_str: Vec<String> = set.iter().map(|node| format!("{}", node)).collect();
+            println!("{} {} {} {}", start, stop, id, set_str.join(" "));
+        }
+    }
     // CALC
     else if args.cmd_calc {
         let delta = delta.unwrap();
         let nbNodes = nbNodes.unwrap();
         // CONNEXITY
         if args.cmd_connexity {
-            // TIME
-            if args.cmd_time {
-                let start = start.unwrap();
-                let stop = stop.unwrap();
-                algo::is_delta_connected(&mut stdinLinks, delta, nbNodes,
-                                         &|_| true,
-                                         &move |time: Time| {time >= start && time < stop});
-            }
-            // NODE
-            else if args.cmd_node {
-                let nodes: Vec<Node> = nodes.unwrap();
-                algo::is_delta_connected(&mut stdinLinks, delta, nbNodes,
-                                         &move |node: Node| { nodes.contains(&node) },
-                                         &|_| true);
-            }
-            // BOTH
-            else if args.cmd_both {
-                let start = start.unwrap();
-                let stop = stop.unwrap();
-                let nodes: Vec<Node> = nodes.unwrap();
-                algo::is_delta_connected(&mut stdinLinks, delta, nbNodes,
-                                         &move |node: Node| { nodes.contains(&node) },
-                                         &move |time: Time| {time >= start && time < stop});
-            }
-            // N/A
-            else {
-                algo::is_delta_connected(&mut stdinLinks, delta, nbNodes, &|_| true, &|_| true);
+            let nfilter: Box<NodeFilter> = match nodes {
+                Some(nodes) => Box::new(move |node: Node| nodes.contains(&node)),
+                None => Box::new(|_| true),
+            };
+            let tfilter: Box<TimeFilter> = match (start, stop) {
+                (Some(start), Some(stop)) => Box::new(move |time: Time| time >= start && time < stop),
+                _ => Box::new(|_| true),
+            };
+            for (time, connected) in algo::is_delta_connected(&mut stdinLinks, delta, nbNodes, &*nfilter, &*tfilter) {
+                println!("{} {}", time, connected);
             }
         }
         // COMPS
@@ -208,8 +624,31 @@ fn main() {
             if args.cmd_node || args.cmd_both {
                 filter = nodes.unwrap().clone();
             }
+            // EXACT
+            if args.cmd_exact {
+                let budget = args.flag_budget.parse::<usize>().unwrap();
+                let tfilter: Box<TimeFilter> = if args.cmd_both {
+                    let start = start.unwrap();
+                    let stop = stop.unwrap();
+                    Box::new(move |time: Time| {time >=start && time <= stop})
+                } else {
+                    Box::new(|_| true)
+                };
+                let reach_graph = algo::delta_reachability_graph(&mut stdinLinks, delta, nbNodes, &|_| true, &*tfilter);
+                let (comps, complete) = algo::reach_components_exact(&reach_graph, &filter, budget);
+                let (lower, lower_restes) = algo::reach_components_lower(&reach_graph, &filter);
+                let (upper, upper_restes) = algo::reach_components_upper(&reach_graph, &filter);
+                let maxcomp = |sets: &[Vec<Node>]| sets.iter().map(|set| set.len()).max().unwrap_or(0);
+                println!("{} {} {} {} {} {:?}",
+                         comps.len(),
+                         maxcomp(&comps),
+                         std::cmp::max(maxcomp(&lower), maxcomp(&lower_restes)),
+                         std::cmp::max(maxcomp(&upper), maxcomp(&upper_restes)),
+                         complete,
+                         comps);
+            }
             // BOTH
-            if args.cmd_both {
+            else if args.cmd_both {
                 let start = start.unwrap();
                 let stop = stop.unwrap();
                 let (comps, restes) = if args.cmd_up {
@@ -258,11 +697,33 @@ fn main() {
         // EXIST
         else if args.cmd_exist {
             let nodes: Vec<usize> = (0..nbNodes).collect();
+            // LR UNORDERED
+            if args.cmd_lr && args.flag_unordered {
+                let min_duration = args.flag_min_duration.map(|d| d.parse::<Time>().unwrap());
+                let trace = algo::delta_existence(&mut stdinLinks, &nodes, delta);
+                let (set, start, stop) = algo::largest_unordered_rectangle(&trace, trace.len(), nbNodes, min_duration);
+                let vec = algo::boolvec_to_set(&set);
+                println!("{} {} {} {} {:?}", start, stop, (stop-start)*(vec.len() as Time), vec.len(), vec);
+            }
             // LR
-            if args.cmd_lr {
+            else if args.cmd_lr {
                 let (start, stop, vec) = algo::largest_boxe(&mut stdinLinks, &nodes, delta);
                 println!("{} {} {} {} {:?}", start, stop, (stop-start)*(vec.len() as Time), vec.len(), vec);
             }
+            // BOXES
+            else if args.cmd_boxes {
+                let top = args.flag_top.parse::<usize>().unwrap();
+                let min_nodes = args.flag_min_nodes.parse::<usize>().unwrap();
+                let trace = algo::delta_existence(&mut stdinLinks, &nodes, delta);
+                let boxes = if top == 0 {
+                    algo::maximal_boxes(&trace, min_nodes)
+                } else {
+                    algo::top_boxes(&trace, top, min_nodes)
+                };
+                for (start, stop, vec) in boxes {
+                    println!("{} {} {} {} {:?}", start, stop, (stop-start)*(vec.len() as Time), vec.len(), vec);
+                }
+            }
             // CUT
             else if args.cmd_cut {
                 let nodes: Vec<usize> = (0..nbNodes).collect();